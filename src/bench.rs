@@ -0,0 +1,156 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+  str::FromStr,
+  time::Instant,
+};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs, CLF_FORMAT},
+  hduration::HDuration,
+  Handler,
+};
+
+/// Shapes of synthetic input, selected by passing @name as the format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Sample {
+  /// Epoch stamps in the precision given by -p
+  Stamp,
+  /// RFC 3339 date-times with a UTC offset
+  Rfc3339,
+  /// Apache/nginx Common Log Format times, such as [19/Mar/2023:16:33:42 -0400]
+  Clf,
+}
+
+impl FromStr for Sample {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let name = s.strip_prefix('@').unwrap_or(s);
+    <Sample as ValueEnum>::from_str(name, true).map_err(|_| {
+      let names = Sample::value_variants()
+        .iter()
+        .filter_map(|p| p.to_possible_value())
+        .map(|p| format!("@{}", p.get_name()))
+        .collect::<Vec<_>>()
+        .join(", ");
+      format!("unknown sample format, expected one of {}", names)
+    })
+  }
+}
+
+impl Sample {
+  fn render(&self, dt: &DateTime<Utc>, args: &FormatArgs) -> Result<String, String> {
+    Ok(match self {
+      Sample::Stamp => args.precision.output().as_stamp(dt)?.to_string(),
+      Sample::Rfc3339 => dt.to_rfc3339(),
+      Sample::Clf => dt
+        .with_timezone(&chrono::FixedOffset::west_opt(4 * 3600).unwrap())
+        .format(&format!("[{}]", CLF_FORMAT))
+        .to_string(),
+    })
+  }
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Number of synthetic lines to convert
+  #[arg(long, short = 'n', default_value_t = 100_000)]
+  lines: usize,
+
+  /// Shape of the synthetic input: @stamp, @rfc3339, or @clf
+  #[arg(long = "format", default_value = "@stamp", value_parser = |s: &str| s.parse::<Sample>())]
+  sample: Sample,
+}
+
+/// Generates lines a second and a bit apart, so no two share a stamp
+fn generate(sample: Sample, lines: usize, args: &FormatArgs) -> Result<Vec<String>, String> {
+  let start = Utc.timestamp_opt(1679258022, 0).unwrap();
+  (0..lines as i64)
+    .map(|i| start + Duration::milliseconds(i * 1_337))
+    .map(|dt| sample.render(&dt, args))
+    .collect()
+}
+
+impl Handler for BenchArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let lines = match generate(self.sample, self.lines, &self.format) {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(lines) => lines,
+    };
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let mut sink = io::sink();
+
+    let started = Instant::now();
+    for line in &lines {
+      let converted = line
+        .parse::<ConversionInput>()
+        .and_then(|inp| inp.to_dt(&self.format.precision, &now))
+        .and_then(|dt| self.format.format(&dt.with_timezone(&into_tz)));
+      match converted {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(text) => writeln!(&mut sink, "{}", text)?,
+      }
+    }
+    let elapsed = started.elapsed();
+
+    let per_sec = lines.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    writeln!(&mut out, "lines:      {}", lines.len())?;
+    writeln!(
+      &mut out,
+      "elapsed:    {}",
+      HDuration::new(elapsed.as_secs(), elapsed.subsec_nanos(), false)
+    )?;
+    writeln!(&mut out, "throughput: {:.0} lines/s", per_sec)?;
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::{BenchArgs, Sample};
+  use crate::run_test;
+
+  #[rstest]
+  #[case("@stamp", "1679258022000")]
+  #[case("rfc3339", "2023-03-19T20:33:42+00:00")]
+  #[case("@CLF", "[19/Mar/2023:16:33:42 -0400]")]
+  fn samples(#[case] name: &str, #[case] first: &str) {
+    use clap::FromArgMatches;
+    let cmd = <BenchArgs as clap::Args>::augment_args(clap::Command::new("bench"));
+    let matches = cmd.get_matches_from(["bench", "--format", name, "-n", "2"]);
+    let args = BenchArgs::from_arg_matches(&matches).unwrap();
+    let lines = super::generate(args.sample, args.lines, &args.format).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], first);
+  }
+
+  #[test]
+  fn unknown_sample() {
+    assert!("@nope".parse::<Sample>().is_err())
+  }
+
+  #[test]
+  fn reports() {
+    let (output, error) = run_test(" bench -n 10 --format @clf");
+    assert_eq!("", error);
+    assert!(output.starts_with("lines:      10\n"));
+    assert!(output.contains("lines/s"));
+  }
+}
@@ -0,0 +1,205 @@
+use std::{
+  collections::BTreeSet,
+  io::{self, IsTerminal, Write},
+  process::ExitCode,
+};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, Precision, StampPrecision, WeekStart, WeekStartArgs},
+  Handler,
+};
+
+/// Reverse video for the given times, and underline for today
+const MARKED: &str = "\x1b[7m";
+const TODAY: &str = "\x1b[4m";
+const BOTH: &str = "\x1b[4;7m";
+const RESET: &str = "\x1b[0m";
+
+/// The width of a week row, seven two digit days apart
+const WIDTH: usize = 20;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Color {
+  /// Highlight only when printing to a terminal
+  Auto,
+  /// Highlight even when piped
+  Always,
+  /// Print plain digits
+  Never,
+}
+
+#[derive(Args)]
+pub struct CalArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  /// What precision timestamps should be treated as
+  #[arg(value_enum, long, short, default_value_t = StampPrecision::Fixed(Precision::Millis), env = "EPC_PRECISION")]
+  precision: StampPrecision,
+
+  #[command(flatten)]
+  week: WeekStartArgs,
+
+  /// Whether to highlight the given times and today with terminal escapes
+  #[arg(value_enum, long, default_value_t = Color::Auto)]
+  color: Color,
+
+  /// Times to highlight, showing every month they fall in. Omitting shows
+  /// the current month
+  #[arg(allow_negative_numbers = true)]
+  input: Vec<ConversionInput>,
+}
+
+/// Renders the month holding the first day as a grid of weeks, highlighting
+/// the marked dates and today when styled
+fn month(
+  first: NaiveDate,
+  marked: &BTreeSet<NaiveDate>,
+  today: NaiveDate,
+  week_start: WeekStart,
+  styled: bool,
+) -> Vec<String> {
+  let title = first.format("%B %Y").to_string();
+  let header = match week_start {
+    WeekStart::Mon => "Mo Tu We Th Fr Sa Su",
+    WeekStart::Sun => "Su Mo Tu We Th Fr Sa",
+  };
+  let mut lines = vec![
+    format!("{:^WIDTH$}", title).trim_end().to_string(),
+    header.to_string(),
+  ];
+  let mut cells = vec!["  ".to_string(); week_start.days_into(first.weekday()) as usize];
+  let days = first.iter_days().take_while(|d| d.month() == first.month());
+  for day in days {
+    let style = match (marked.contains(&day), day == today) {
+      _ if !styled => None,
+      (true, true) => Some(BOTH),
+      (true, false) => Some(MARKED),
+      (false, true) => Some(TODAY),
+      (false, false) => None,
+    };
+    cells.push(match style {
+      Some(style) => format!("{}{:>2}{}", style, day.day(), RESET),
+      None => format!("{:>2}", day.day()),
+    });
+    if week_start.days_into(day.weekday()) == 6 {
+      lines.push(cells.join(" "));
+      cells.clear();
+    }
+  }
+  if !cells.is_empty() {
+    lines.push(cells.join(" "));
+  }
+  lines
+}
+
+impl Handler for CalArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let resolved = self
+      .input
+      .iter()
+      .map(|inp| {
+        inp
+          .to_dt(&self.precision, &now)
+          .map(|dt| dt.with_timezone(&into_tz).date_naive())
+      })
+      .collect::<Result<BTreeSet<_>, _>>();
+    let marked = match resolved {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(marked) => marked,
+    };
+    let today = now.date_naive();
+    let firsts = match marked.is_empty() {
+      true => BTreeSet::from([today]),
+      false => marked.clone(),
+    }
+    .into_iter()
+    .filter_map(|d| d.with_day(1))
+    .collect::<BTreeSet<_>>();
+    let styled = match self.color {
+      Color::Auto => io::stdout().is_terminal(),
+      Color::Always => true,
+      Color::Never => false,
+    };
+    for (i, first) in firsts.into_iter().enumerate() {
+      if i > 0 {
+        writeln!(&mut out)?;
+      }
+      for line in month(first, &marked, today, self.week.get(), styled) {
+        writeln!(&mut out, "{}", line)?;
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::BTreeSet;
+
+  use chrono::NaiveDate;
+  use indoc::indoc;
+
+  use super::month;
+  use crate::{common::WeekStart, run_test};
+
+  #[test]
+  fn grid() {
+    let first = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+    let marked = BTreeSet::from([NaiveDate::from_ymd_opt(2023, 3, 17).unwrap()]);
+    let today = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
+    assert_eq!(
+      month(first, &marked, today, WeekStart::Mon, true).join("\n") + "\n",
+      indoc! {"
+             March 2023
+        Mo Tu We Th Fr Sa Su
+               1  2  3  4  5
+         6  7  8  9 10 11 12
+        13 14 15 16 \x1b[7m17\x1b[0m 18 19
+        \x1b[4m20\x1b[0m 21 22 23 24 25 26
+        27 28 29 30 31
+      "}
+    );
+  }
+
+  #[test]
+  fn months() {
+    let (output, error) =
+      run_test(" cal -t=UTC -p secs --color always 1675209600 1677542400 1677628800");
+    assert_eq!("", error);
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "   February 2023");
+    assert!(lines.contains(&"     March 2023"));
+    assert!(lines.contains(&"      \x1b[7m 1\x1b[0m  2  3  4  5"));
+  }
+
+  #[test]
+  fn sunday_start() {
+    let (output, error) =
+      run_test(" cal -t=UTC -p secs --color always --week-start sun 1677628800");
+    assert_eq!("", error);
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[1], "Su Mo Tu We Th Fr Sa");
+    assert_eq!(lines[2], "         \x1b[7m 1\x1b[0m  2  3  4");
+    assert_eq!(lines[3], " 5  6  7  8  9 10 11");
+  }
+
+  #[test]
+  fn plain() {
+    let (output, error) = run_test(" cal -t=UTC -p secs --color never 1677628800");
+    assert_eq!("", error);
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "     March 2023");
+    assert_eq!(lines[2], "       1  2  3  4  5");
+    assert!(!output.contains('\x1b'));
+  }
+}
@@ -0,0 +1,140 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+  str::FromStr,
+};
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveTime, Utc};
+use clap::Args;
+
+use crate::{
+  common::{relocal, AtTimezoneArgs, ConversionInput, FormatArgs},
+  Handler,
+};
+
+/// A month named as YYYY-MM, or by any time within it
+#[derive(Clone)]
+enum MonthInput {
+  Month(NaiveDate),
+  Time(ConversionInput),
+}
+
+impl FromStr for MonthInput {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d") {
+      Ok(first) => Ok(MonthInput::Month(first)),
+      Err(_) => s.parse().map(MonthInput::Time),
+    }
+  }
+}
+
+#[derive(Args)]
+pub struct CalInfoArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Months to describe, as YYYY-MM or any time within them
+  #[arg(allow_negative_numbers = true, default_value = "now")]
+  input: Vec<MonthInput>,
+}
+
+fn is_leap(year: i32) -> bool {
+  NaiveDate::from_ymd_opt(year, 2, 29).is_some()
+}
+
+impl Handler for CalInfoArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    for (i, input) in self.input.iter().enumerate() {
+      let first = match input {
+        MonthInput::Month(first) => Ok(*first),
+        MonthInput::Time(time) => time.to_dt(&self.format.precision, &now).and_then(|dt| {
+          let date = dt.with_timezone(&into_tz).date_naive();
+          date
+            .with_day(1)
+            .ok_or_else(|| format!("{} has no first day", date))
+        }),
+      };
+      let bounds = first.and_then(|first| {
+        let next = first
+          .checked_add_months(Months::new(1))
+          .ok_or("The month is out of range")?;
+        let start = relocal(&now, &first.and_time(NaiveTime::MIN))?;
+        let end = relocal(&now, &next.and_time(NaiveTime::MIN))? - Duration::nanoseconds(1);
+        Ok((
+          first,
+          end.day(),
+          self.format.format(&start)?,
+          self.format.format(&end)?,
+        ))
+      });
+      let (first, days, start, end) = match bounds {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(v) => v,
+      };
+      if i > 0 {
+        writeln!(&mut out)?;
+      }
+      writeln!(&mut out, "month: {}", first.format("%Y-%m"))?;
+      writeln!(
+        &mut out,
+        "leap year: {}",
+        if is_leap(first.year()) { "yes" } else { "no" }
+      )?;
+      writeln!(&mut out, "days: {}", days)?;
+      writeln!(&mut out, "first: {}", start)?;
+      writeln!(&mut out, "last: {}", end)?;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use indoc::indoc;
+  use rstest::*;
+
+  use super::is_leap;
+  use crate::run_test;
+
+  #[rstest]
+  #[case(2024, true)]
+  #[case(2023, false)]
+  #[case(1900, false)]
+  #[case(2000, true)]
+  fn leap(#[case] year: i32, #[case] expected: bool) {
+    assert_eq!(is_leap(year), expected)
+  }
+
+  #[test]
+  fn months() {
+    let (output, error) = run_test(" cal-info -t=UTC -p secs 2024-02 1689379200");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        month: 2024-02
+        leap year: yes
+        days: 29
+        first: 1706745600
+        last: 1709251199
+
+        month: 2023-07
+        leap year: no
+        days: 31
+        first: 1688169600
+        last: 1690847999
+      "},
+      output
+    );
+  }
+}
@@ -0,0 +1,168 @@
+use std::{
+  io::{self, Write},
+  net::TcpStream,
+  path::Path,
+  process::ExitCode,
+  sync::Arc,
+  time::SystemTime,
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use clap::Args;
+use rustls::{
+  client::{ServerCertVerified, ServerCertVerifier},
+  Certificate, ClientConfig, ClientConnection, ServerName,
+};
+use x509_parser::{certificate::X509Certificate, pem::parse_x509_pem, prelude::FromDer};
+
+use crate::{
+  common::{AtTimezoneArgs, DurationArgs, FormatArgs},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct CertArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// A PEM or DER encoded certificate file, or a host:port to read the
+  /// certificate from over TLS
+  #[arg()]
+  source: String,
+}
+
+impl Handler for CertArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let der = if Path::new(&self.source).is_file() {
+      std::fs::read(&self.source).map_err(|e| format!("Could not read {}: {}", self.source, e))
+    } else {
+      fetch_leaf(&self.source)
+    };
+    let into_tz = self.timezone.get();
+    let validity = der
+      .and_then(|der| validity(&der))
+      .and_then(|(not_before, not_after)| {
+        let before = self.format.format(&not_before.with_timezone(&into_tz))?;
+        let after = self.format.format(&not_after.with_timezone(&into_tz))?;
+        let remaining = self.duration.format(not_after - Utc::now())?;
+        Ok((before, after, remaining))
+      });
+    let (before, after, remaining) = match validity {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+
+    writeln!(&mut out, "not before: {}", before)?;
+    writeln!(&mut out, "not after:  {}", after)?;
+    writeln!(&mut out, "remaining:  {}", remaining)?;
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+/// Reads the validity window from the first certificate in a PEM or DER blob
+fn validity(bytes: &[u8]) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+  let pem;
+  let der = if bytes.starts_with(b"-----BEGIN") {
+    pem = parse_x509_pem(bytes)
+      .map_err(|e| format!("Could not read PEM: {}", e))?
+      .1;
+    &pem.contents
+  } else {
+    bytes
+  };
+  let (_, cert) =
+    X509Certificate::from_der(der).map_err(|e| format!("Could not parse certificate: {}", e))?;
+  let to_dt = |ts: i64| {
+    Utc
+      .timestamp_opt(ts, 0)
+      .single()
+      .ok_or_else(|| format!("Certificate date out of range: {}", ts))
+  };
+  let validity = cert.validity();
+  Ok((
+    to_dt(validity.not_before.timestamp())?,
+    to_dt(validity.not_after.timestamp())?,
+  ))
+}
+
+/// Accepts any certificate, since the point is to inspect them, expired or not
+struct AcceptAny;
+
+impl ServerCertVerifier for AcceptAny {
+  fn verify_server_cert(
+    &self,
+    _end_entity: &Certificate,
+    _intermediates: &[Certificate],
+    _server_name: &ServerName,
+    _scts: &mut dyn Iterator<Item = &[u8]>,
+    _ocsp_response: &[u8],
+    _now: SystemTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    Ok(ServerCertVerified::assertion())
+  }
+}
+
+/// Completes a TLS handshake with host:port and returns the DER of the leaf certificate
+fn fetch_leaf(addr: &str) -> Result<Vec<u8>, String> {
+  let (host, _) = addr
+    .rsplit_once(':')
+    .ok_or_else(|| format!("{} is neither a file nor a host:port", addr))?;
+  let config = ClientConfig::builder()
+    .with_safe_defaults()
+    .with_custom_certificate_verifier(Arc::new(AcceptAny))
+    .with_no_client_auth();
+  let server_name =
+    ServerName::try_from(host).map_err(|e| format!("Invalid host {}: {}", host, e))?;
+  let mut conn = ClientConnection::new(Arc::new(config), server_name)
+    .map_err(|e| format!("Could not start TLS: {}", e))?;
+  let mut sock = TcpStream::connect(addr).map_err(|e| format!("Could not connect: {}", e))?;
+  while conn.is_handshaking() {
+    conn
+      .complete_io(&mut sock)
+      .map_err(|e| format!("TLS handshake failed: {}", e))?;
+  }
+  conn
+    .peer_certificates()
+    .and_then(|certs| certs.first())
+    .map(|cert| cert.0.clone())
+    .ok_or_else(|| format!("{} presented no certificates", addr))
+}
+
+#[cfg(test)]
+mod test {
+  use super::validity;
+
+  const PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBfDCCASGgAwIBAgIUdJ0VKwMhq4Mu9awdSenlaeNfFNgwCgYIKoZIzj0EAwIw
+EzERMA8GA1UEAwwIZXBjLnRlc3QwHhcNMjMwMzE5MDAwMDAwWhcNMjQwMzE5MDAw
+MDAwWjATMREwDwYDVQQDDAhlcGMudGVzdDBZMBMGByqGSM49AgEGCCqGSM49AwEH
+A0IABPY8V8do20tI/HEsZ6+FXPABmMM+lOiF0uK1TvXfoBWVBkIILnYxIvUU2uWe
+sN6pFt1IQKK0Sa1dOTyPBo87l/+jUzBRMB0GA1UdDgQWBBT14PJnMOff2tS7QlrD
+tvOQrq9fXjAfBgNVHSMEGDAWgBT14PJnMOff2tS7QlrDtvOQrq9fXjAPBgNVHRMB
+Af8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDl4g2+hTQ8gYMN336teGcf38No
+7tifVKlTOwCFX0gguAIhANO12XKaNN+D7Yu/xL5fvUyU4AySE+1miTbKMSnL1g/o
+-----END CERTIFICATE-----
+";
+
+  #[test]
+  fn pem_validity() {
+    let (not_before, not_after) = validity(PEM.as_bytes()).unwrap();
+    assert_eq!(not_before.timestamp(), 1679184000);
+    assert_eq!(not_after.timestamp(), 1710806400);
+  }
+
+  #[test]
+  fn garbage() {
+    assert!(validity(b"not a certificate").is_err());
+  }
+}
@@ -1,11 +1,41 @@
+mod bounds;
+mod business;
 mod calc;
+mod coarsen;
+mod dst;
+mod duration;
+mod epoch;
 mod formatting;
+mod holidays;
+mod input;
 mod precision;
+mod relative;
+mod schedule;
+mod shift;
+mod snap;
+mod solar;
 mod timezone;
 mod truncate;
+mod weekstart;
+mod zone;
 
+pub use bounds::BoundsArgs;
+pub use business::{business_time, BusinessHours};
 pub use calc::CalcArgs;
-pub use formatting::FormatArgs;
-pub use precision::Precision;
-pub use timezone::AtTimezoneArgs;
+pub use coarsen::CoarsenArgs;
+pub use dst::{relocal, Dst};
+pub use duration::DurationArgs;
+pub use epoch::named_scheme;
+pub use formatting::{relative, FormatArgs, DEFAULT_FORMAT};
+pub use holidays::{HolidayArgs, Holidays};
+pub use input::{ConversionInput, CLF_FORMAT};
+pub use precision::{Precision, Rounding, StampPrecision, JS_MAX_SAFE};
+pub use relative::RelativeInput;
+pub use schedule::Schedule;
+pub use shift::ShiftArgs;
+pub use snap::SnapArgs;
+pub use solar::SolarArgs;
+pub use timezone::{place_candidates, AtTimezoneArgs, AutoTz, InputTimezoneArgs};
 pub use truncate::TruncateArgs;
+pub use weekstart::{WeekStart, WeekStartArgs};
+pub use zone::Zone;
@@ -0,0 +1,65 @@
+use clap::{Args, ValueEnum};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Bounds {
+  /// Both the start and end of a window are included
+  Closed,
+  /// The start of a window is included but its end is not
+  HalfOpen,
+}
+
+#[derive(Args)]
+pub struct BoundsArgs {
+  /// Whether the end of a window counts as inside it. Times only left out for
+  /// landing on the end of half-open bounds are noted on stderr
+  #[arg(value_enum, long, default_value_t = Bounds::Closed)]
+  pub bounds: Bounds,
+}
+
+impl BoundsArgs {
+  /// Whether the value falls between start and end, in either order
+  pub fn contains<T: PartialOrd>(&self, start: &T, end: &T, v: &T) -> bool {
+    let (lo, hi) = if start <= end {
+      (start, end)
+    } else {
+      (end, start)
+    };
+    if v == end {
+      return self.bounds == Bounds::Closed;
+    }
+    lo <= v && v <= hi
+  }
+
+  /// Whether the value sits on the end and was only left out for the bounds
+  /// being half-open, which callers note so the choice is never silent
+  pub fn cuts<T: PartialEq>(&self, v: &T, end: &T) -> bool {
+    self.bounds == Bounds::HalfOpen && v == end
+  }
+
+  /// Whether an ascending value has yet to pass the end
+  pub fn short_of<T: PartialOrd>(&self, v: &T, end: &T) -> bool {
+    v < end || (v == end && self.bounds == Bounds::Closed)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::{Bounds, BoundsArgs};
+
+  #[rstest]
+  #[case(Bounds::Closed, 10, true)]
+  #[case(Bounds::HalfOpen, 10, false)]
+  #[case(Bounds::HalfOpen, 0, true)]
+  #[case(Bounds::HalfOpen, 5, true)]
+  #[case(Bounds::Closed, 11, false)]
+  fn contains(#[case] bounds: Bounds, #[case] v: i64, #[case] expected: bool) {
+    let args = BoundsArgs { bounds };
+    assert_eq!(args.contains(&0, &10, &v), expected);
+    // Counting down, the end is the low side
+    assert_eq!(args.contains(&10, &0, &(10 - v)), expected);
+    // Only the end itself is ever cut, and only by half-open bounds
+    assert_eq!(args.cuts(&v, &10), !expected && v == 10);
+  }
+}
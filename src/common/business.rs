@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Weekday};
+
+use super::Holidays;
+
+/// The daily window of working time, such as 09:00-17:00
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BusinessHours {
+  start: Duration,
+  end: Duration,
+}
+
+impl BusinessHours {
+  pub fn length(&self) -> Duration {
+    self.end - self.start
+  }
+}
+
+impl FromStr for BusinessHours {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (start, end) = s
+      .split_once('-')
+      .ok_or_else(|| format!("Expected HH:MM-HH:MM but got: {}", s))?;
+    let parse = |t: &str| {
+      if t == "24:00" {
+        return Ok(Duration::days(1));
+      }
+      NaiveTime::parse_from_str(t, "%H:%M")
+        .map(|t| t - NaiveTime::MIN)
+        .map_err(|_| format!("Invalid time of day: {}", t))
+    };
+    let hours = BusinessHours {
+      start: parse(start)?,
+      end: parse(end)?,
+    };
+    if hours.end <= hours.start {
+      return Err(format!("Business hours must end after they start: {}", s));
+    }
+    Ok(hours)
+  }
+}
+
+fn is_business_day(day: Weekday) -> bool {
+  !matches!(day, Weekday::Sat | Weekday::Sun)
+}
+
+/// Moves the date by a count of business days, skipping weekends and the given
+/// holidays. Negative counts move backwards
+pub fn add_business_days(date: NaiveDate, days: i64, holidays: &Holidays) -> Option<NaiveDate> {
+  let mut date = date;
+  for _ in 0..days.unsigned_abs() {
+    loop {
+      date = match days < 0 {
+        true => date.pred_opt()?,
+        false => date.succ_opt()?,
+      };
+      if is_business_day(date.weekday()) && !holidays.contains(date) {
+        break;
+      }
+    }
+  }
+  Some(date)
+}
+
+/// Sums the working time between two instants, counting only the business
+/// hours of weekdays that are not holidays in the instants' timezone. Negative
+/// when `to` is earlier
+pub fn business_time<T: TimeZone>(
+  from: &DateTime<T>,
+  to: &DateTime<T>,
+  hours: &BusinessHours,
+  holidays: &Holidays,
+) -> Duration {
+  if to < from {
+    return -business_time(to, from, hours, holidays);
+  }
+  let tz = from.timezone();
+  from
+    .date_naive()
+    .iter_days()
+    .take_while(|date| *date <= to.date_naive())
+    .filter(|date| is_business_day(date.weekday()) && !holidays.contains(*date))
+    .filter_map(|date| {
+      let midnight = date.and_time(NaiveTime::MIN);
+      let open = tz
+        .from_local_datetime(&(midnight + hours.start))
+        .earliest()?;
+      let close = tz.from_local_datetime(&(midnight + hours.end)).latest()?;
+      let start = open.max(from.clone());
+      let end = close.min(to.clone());
+      (end > start).then(|| end - start)
+    })
+    .fold(Duration::zero(), |total, worked| total + worked)
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{DateTime, Duration, FixedOffset};
+  use rstest::*;
+
+  use super::{add_business_days, business_time, BusinessHours};
+  use crate::common::Holidays;
+
+  fn dt(s: &str) -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339(s).unwrap()
+  }
+
+  #[rstest]
+  // Friday plus one lands on Monday, and a weekend start counts from it
+  #[case("2023-03-17", 1, &[], "2023-03-20")]
+  #[case("2023-03-18", 1, &[], "2023-03-20")]
+  #[case("2023-03-15", 5, &[], "2023-03-22")]
+  #[case("2023-03-20", -1, &[], "2023-03-17")]
+  #[case("2023-03-17", 0, &[], "2023-03-17")]
+  #[case("2023-03-17", 2, &["2023-03-20"], "2023-03-22")]
+  #[case("2023-03-21", -1, &["2023-03-20"], "2023-03-17")]
+  fn business_days(
+    #[case] from: &str,
+    #[case] days: i64,
+    #[case] holidays: &[&str],
+    #[case] expected: &str,
+  ) {
+    let holidays = holidays.join("\n").parse::<Holidays>().unwrap();
+    assert_eq!(
+      add_business_days(from.parse().unwrap(), days, &holidays),
+      expected.parse().ok()
+    )
+  }
+
+  #[rstest]
+  #[case("09:00-17:00", Ok(8))]
+  #[case("00:00-24:00", Ok(24))]
+  #[case("17:00-09:00", Err(()))]
+  #[case("9-5", Err(()))]
+  fn parse(#[case] input: &str, #[case] hours: Result<i64, ()>) {
+    let parsed = input.parse::<BusinessHours>();
+    assert_eq!(
+      parsed.map(|h| h.length().num_hours()).map_err(|_| ()),
+      hours
+    )
+  }
+
+  #[rstest]
+  // Friday afternoon to Monday morning skips the weekend
+  #[case("2023-03-17T15:00:00Z", "2023-03-20T10:00:00Z", 3)]
+  // Wholly outside business hours
+  #[case("2023-03-17T18:00:00Z", "2023-03-20T08:00:00Z", 0)]
+  // Two full weeks
+  #[case("2023-03-06T00:00:00Z", "2023-03-20T00:00:00Z", 80)]
+  #[case("2023-03-20T10:00:00Z", "2023-03-17T15:00:00Z", -3)]
+  fn business_hours(#[case] from: &str, #[case] to: &str, #[case] hours: i64) {
+    let window = "09:00-17:00".parse::<BusinessHours>().unwrap();
+    assert_eq!(
+      business_time(&dt(from), &dt(to), &window, &Holidays::default()),
+      Duration::hours(hours)
+    );
+  }
+}
@@ -1,27 +1,413 @@
-use std::ops::Neg;
+use std::{fmt::Display, str::FromStr};
 
-use chrono::DateTime;
-use chrono_tz::Tz;
-use clap::Args;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, SecondsFormat, TimeZone, Utc};
+use clap::{Args, ValueEnum};
 
+use super::{business::add_business_days, dst::relocal, HolidayArgs, Zone};
 use crate::hduration::HDuration;
 
+/// A human friendly duration that may also hold months (mo) and years (y),
+/// which move the calendar date rather than adding a fixed length
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarDuration {
+  /// Whole months, negative to move back
+  months: i64,
+  /// The fixed part, carrying its own sign
+  fixed: HDuration,
+}
+
+impl FromStr for CalendarDuration {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s.strip_prefix('-').unwrap_or(s).starts_with(['P', 'p']) {
+      return s.parse().map(|fixed| CalendarDuration { months: 0, fixed });
+    }
+    // A minus sign makes its word and every word after it negative
+    let mut negative = false;
+    let mut months: i64 = 0;
+    let (mut ahead, mut behind) = (Vec::new(), Vec::new());
+    for word in s.split(' ') {
+      let word = match word.strip_prefix('-') {
+        Some(rest) => {
+          negative = true;
+          rest
+        }
+        None => word,
+      };
+      // Split runs like 1y2mo3d at each number following a unit
+      let mut start = 0;
+      let mut segments = Vec::new();
+      for (i, c) in word.char_indices() {
+        let after_unit = word[..i].ends_with(|c: char| c.is_ascii_alphabetic());
+        if i > start && after_unit && (c.is_ascii_digit() || c == '.') {
+          segments.push(&word[start..i]);
+          start = i;
+        }
+      }
+      segments.push(&word[start..]);
+      for segment in segments {
+        let number = segment.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let per = match &segment[number.len()..] {
+          "mo" => 1,
+          "y" => 12,
+          _ => {
+            match negative {
+              true => behind.push(segment),
+              false => ahead.push(segment),
+            }
+            continue;
+          }
+        };
+        let signed = if negative { -per } else { per };
+        months = number
+          .parse::<u32>()
+          .ok()
+          .and_then(|n| months.checked_add(n as i64 * signed))
+          .ok_or_else(|| format!("{} is not a whole number of months or years", segment))?;
+      }
+    }
+    let fixed = |words: Vec<&str>| match words.is_empty() {
+      true => Ok(HDuration::new(0, 0, false)),
+      false => words.join(" ").parse::<HDuration>(),
+    };
+    let (ahead, behind) = (fixed(ahead)?.inner, fixed(behind)?.inner);
+    let fixed = match ahead >= behind {
+      true => HDuration {
+        inner: ahead - behind,
+        negative: false,
+      },
+      false => HDuration {
+        inner: behind - ahead,
+        negative: true,
+      },
+    };
+    Ok(CalendarDuration { months, fixed })
+  }
+}
+
+impl CalendarDuration {
+  fn negated(&self) -> Self {
+    CalendarDuration {
+      months: -self.months,
+      fixed: HDuration {
+        inner: self.fixed.inner,
+        negative: !self.fixed.negative,
+      },
+    }
+  }
+}
+
+impl Display for CalendarDuration {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let (mut ahead, mut behind) = (Vec::new(), Vec::new());
+    let months = self.months.unsigned_abs();
+    let parts = if self.months < 0 {
+      &mut behind
+    } else {
+      &mut ahead
+    };
+    if months >= 12 {
+      parts.push(format!("{}y", months / 12));
+    }
+    if !months.is_multiple_of(12) {
+      parts.push(format!("{}mo", months % 12));
+    }
+    if months == 0 || !self.fixed.inner.is_zero() {
+      let unsigned = HDuration {
+        inner: self.fixed.inner,
+        negative: false,
+      };
+      match self.fixed.negative {
+        true => behind.push(unsigned.to_string()),
+        false => ahead.push(unsigned.to_string()),
+      }
+    }
+    // Negative parts follow the positive ones, behind a single minus sign
+    let behind = match behind.is_empty() {
+      true => None,
+      false => Some(format!("-{}", behind.join(" "))),
+    };
+    let ahead = (!ahead.is_empty()).then(|| ahead.join(" "));
+    let parts = ahead.into_iter().chain(behind).collect::<Vec<_>>();
+    write!(f, "{}", parts.join(" "))
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Overflow {
+  /// Fail when the result is out of range
+  Error,
+  /// Clamp to the earliest or latest representable time
+  Saturate,
+  /// Wrap around from one end of the representable range to the other
+  Wrap,
+}
+
 #[derive(Args)]
 pub struct CalcArgs {
-  /// Add a human friendly duration to all times (can be negative)
-  #[arg(long, short = 'a', allow_hyphen_values = true)]
-  add: Option<HDuration>,
+  /// Add a human friendly duration to all times (can be negative). Months (mo)
+  /// and years (y) move the calendar date, clamping to the end of shorter
+  /// months, before any fixed units are added. A minus sign negates its part
+  /// and those after it, so "1mo -1h" is a month on and an hour back. Repeat
+  /// to apply several in the order given
+  #[arg(long, short = 'a', allow_hyphen_values = true, group = "changes")]
+  add: Vec<CalendarDuration>,
+
+  /// Subtract a human friendly duration from all times, after any additions.
+  /// Repeat to apply several in the order given
+  #[arg(long, group = "changes")]
+  sub: Vec<CalendarDuration>,
+
+  /// Move all times by a count of business days, skipping weekends, before
+  /// any other additions. Negative counts move backwards
+  #[arg(long, allow_negative_numbers = true, group = "changes")]
+  add_business_days: Option<i64>,
+
+  #[command(flatten)]
+  holidays: HolidayArgs,
+
+  /// Also write a command to stderr that undoes the additions and
+  /// subtractions, restoring the times as they were before them
+  #[arg(long, requires = "changes", conflicts_with = "add_business_days")]
+  inverse: bool,
+
+  /// What to do when an addition leaves the representable range of times
+  #[arg(value_enum, long, default_value_t = Overflow::Error)]
+  overflow: Overflow,
+}
+
+fn nanos<T: TimeZone>(dt: &DateTime<T>) -> i128 {
+  dt.timestamp() as i128 * 1_000_000_000 + dt.timestamp_subsec_nanos() as i128
+}
+
+/// The range of times with a valid local date in every zone, as chrono's own
+/// limits overflow once an offset is applied
+fn bounds() -> (DateTime<Utc>, DateTime<Utc>) {
+  let day = Duration::days(1);
+  (
+    DateTime::<Utc>::MIN_UTC + day,
+    DateTime::<Utc>::MAX_UTC - day,
+  )
+}
+
+/// Moves the local date by whole months, keeping the wall clock time and
+/// clamping to the end of shorter months, then resolves an out of range
+/// result by the overflow policy
+fn add_months(
+  dt: DateTime<Zone>,
+  months: i64,
+  overflow: Overflow,
+) -> Result<DateTime<Zone>, String> {
+  let (min, max) = bounds();
+  let local = dt.naive_local();
+  let moved = u32::try_from(months.unsigned_abs())
+    .ok()
+    .map(Months::new)
+    .and_then(|m| match months < 0 {
+      true => local.checked_sub_months(m),
+      false => local.checked_add_months(m),
+    })
+    .filter(|moved| (min.naive_utc()..=max.naive_utc()).contains(moved));
+  let moved = match (moved, overflow) {
+    (Some(moved), _) => moved,
+    (None, Overflow::Error) => return Err(format!("Adding {} months is out of range", months)),
+    (None, Overflow::Saturate) => {
+      let bound = if months < 0 { min } else { max };
+      return Ok(bound.with_timezone(&dt.timezone()));
+    }
+    (None, Overflow::Wrap) => {
+      // Wrap around the months lying wholly inside the bounds
+      let index = |d: NaiveDate| d.year() as i128 * 12 + d.month0() as i128;
+      let (lo, hi) = (index(min.date_naive()) + 1, index(max.date_naive()) - 1);
+      let wrapped = lo + (index(local.date()) + months as i128 - lo).rem_euclid(hi - lo + 1);
+      // January has every day, so adding months from it clamps the day
+      NaiveDate::from_ymd_opt(wrapped.div_euclid(12) as i32, 1, local.day())
+        .and_then(|jan| jan.checked_add_months(Months::new(wrapped.rem_euclid(12) as u32)))
+        .map(|date| date.and_time(local.time()))
+        .ok_or_else(|| format!("Adding {} months is out of range", months))?
+    }
+  };
+  relocal(&dt, &moved)
+}
+
+/// Adds without chrono's limits, then resolves an out of range result by the
+/// overflow policy
+fn add_with(
+  dt: DateTime<Zone>,
+  dur: &CalendarDuration,
+  overflow: Overflow,
+) -> Result<DateTime<Zone>, String> {
+  let dt = match dur.months {
+    0 => dt,
+    months => add_months(dt, months, overflow)?,
+  };
+  let (min, max) = bounds();
+  let delta = dur.fixed.inner.as_nanos() as i128;
+  let sum = nanos(&dt) + if dur.fixed.negative { -delta } else { delta };
+  let (lo, hi) = (nanos(&min), nanos(&max));
+  let total = match overflow {
+    _ if (lo..=hi).contains(&sum) => sum,
+    Overflow::Error => return Err(format!("Adding {} is out of range", dur)),
+    Overflow::Saturate => sum.clamp(lo, hi),
+    Overflow::Wrap => lo + (sum - lo).rem_euclid(hi - lo + 1),
+  };
+  let secs = total.div_euclid(1_000_000_000) as i64;
+  let subsec = total.rem_euclid(1_000_000_000) as u32;
+  Utc
+    .timestamp_opt(secs, subsec)
+    .single()
+    .map(|utc| utc.with_timezone(&dt.timezone()))
+    .ok_or_else(|| format!("Adding {} is out of range", dur))
 }
 
 impl CalcArgs {
-  pub fn eval(&self, dt: DateTime<Tz>) -> Result<DateTime<Tz>, String> {
-    if let Some(dur) = &self.add {
-      chrono::Duration::from_std(dur.inner)
-        .map(|d| if dur.negative { d.neg() } else { d })
-        .map_err(|e| format!("{}", e))
-        .map(|d| dt + d)
-    } else {
-      Ok(dt)
+  /// Every change in the order applied, with subtractions negated
+  fn steps(&self) -> Vec<CalendarDuration> {
+    let subs = self.sub.iter().map(CalendarDuration::negated);
+    self.add.iter().cloned().chain(subs).collect()
+  }
+
+  /// The command reversing the changes on the given results, when asked for.
+  /// Each is negated and applied in the opposite order
+  pub fn inverse_command<T: TimeZone>(&self, results: &[DateTime<T>]) -> Option<String>
+  where
+    T::Offset: Display,
+  {
+    let steps = self.steps();
+    if !self.inverse || steps.is_empty() {
+      return None;
     }
+    let undo = steps
+      .iter()
+      .rev()
+      .map(|dur| format!("-a=\"{}\"", dur.negated()))
+      .collect::<Vec<_>>()
+      .join(" ");
+    let values = results
+      .iter()
+      .map(|dt| dt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+      .collect::<Vec<_>>()
+      .join(" ");
+    Some(format!("epc convert -p nanos {} {}", undo, values))
+  }
+
+  /// With --inverse, whether undoing the changes on `to` restores `from`.
+  /// Months clamp to the end of shorter ones and DST can move the wall time,
+  /// so undoing those may land elsewhere
+  pub fn undoes(&self, from: &DateTime<Zone>, to: &DateTime<Zone>) -> bool {
+    !self.inverse
+      || self
+        .steps()
+        .iter()
+        .rev()
+        .try_fold(*to, |dt, dur| add_with(dt, &dur.negated(), self.overflow))
+        .is_ok_and(|dt| dt == *from)
+  }
+
+  pub fn eval(&self, dt: DateTime<Zone>) -> Result<DateTime<Zone>, String> {
+    let dt = match self.add_business_days {
+      None => dt,
+      Some(days) => {
+        let local = dt.naive_local();
+        let holidays = self.holidays.load()?;
+        let moved = add_business_days(local.date(), days, &holidays)
+          .ok_or_else(|| format!("Adding {} business days is out of range", days))?;
+        relocal(&dt, &moved.and_time(local.time()))?
+      }
+    };
+    self
+      .steps()
+      .iter()
+      .try_fold(dt, |dt, dur| add_with(dt, dur, self.overflow))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{Datelike, NaiveDate, NaiveTime, TimeZone, Utc};
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::{add_with, bounds, CalendarDuration, Overflow, Zone};
+
+  #[rstest]
+  #[case(Overflow::Error, "1h", Ok(3600))]
+  #[case(Overflow::Error, "-1000000000w", Err(()))]
+  #[case(Overflow::Saturate, "1000000000w", Ok(bounds().1.timestamp()))]
+  #[case(Overflow::Saturate, "-1000000000w", Ok(bounds().0.timestamp()))]
+  fn policy(#[case] overflow: Overflow, #[case] add: &str, #[case] expected: Result<i64, ()>) {
+    let dt = Zone::from(Tz::UTC).timestamp_opt(0, 0).unwrap();
+    let added = add_with(dt, &add.parse().unwrap(), overflow);
+    assert_eq!(added.map(|dt| dt.timestamp()).map_err(|_| ()), expected)
+  }
+
+  #[rstest]
+  #[case("2023-01-31T12:00:00", "1mo", "2023-02-28T12:00:00-05:00")]
+  #[case("2024-01-31T12:00:00", "1mo", "2024-02-29T12:00:00-05:00")]
+  #[case("2024-02-29T12:00:00", "1y", "2025-02-28T12:00:00-05:00")]
+  #[case("2023-03-31T12:00:00", "-1mo 1h", "2023-02-28T11:00:00-05:00")]
+  #[case("2023-03-31T12:00:00", "1mo -1h", "2023-04-30T11:00:00-04:00")]
+  #[case("2023-03-31T12:00:00", "1h -1mo", "2023-02-28T13:00:00-05:00")]
+  #[case("2023-01-15T12:00:00", "1y2mo3d", "2024-03-18T12:00:00-04:00")]
+  #[case("2023-02-12T02:30:00", "1mo", "2023-03-12T03:30:00-04:00")]
+  fn calendar(#[case] start: &str, #[case] add: &str, #[case] expected: &str) {
+    let tz = Zone::from(Tz::America__New_York);
+    let dt = tz.from_local_datetime(&start.parse().unwrap()).unwrap();
+    let added = add_with(dt, &add.parse().unwrap(), Overflow::Error);
+    assert_eq!(added.map(|dt| dt.to_rfc3339()), Ok(expected.into()))
+  }
+
+  #[rstest]
+  #[case("1y2mo3d", Ok("1y 2mo 3d"))]
+  #[case("-14mo", Ok("-1y 2mo"))]
+  #[case("1mo -1h", Ok("1mo -1h"))]
+  #[case("-1mo 1h", Ok("-1mo 1h"))]
+  #[case("1y -1mo", Ok("11mo"))]
+  #[case("1h -30m 1mo", Ok("30m -1mo"))]
+  #[case("90m", Ok("1h 30m"))]
+  #[case("1.5mo", Err(()))]
+  fn calendar_duration(#[case] input: &str, #[case] expected: Result<&str, ()>) {
+    let parsed = input.parse::<CalendarDuration>();
+    assert_eq!(
+      parsed.map(|d| d.to_string()).map_err(|_| ()),
+      expected.map(String::from)
+    )
+  }
+
+  /// Midnight UTC on the date, in seconds
+  fn midnight(year: i32, month: u32, day: u32) -> i64 {
+    let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    Utc
+      .from_utc_datetime(&date.and_time(NaiveTime::MIN))
+      .timestamp()
+  }
+
+  #[rstest]
+  #[case(Overflow::Error, "2mo", Err(()))]
+  #[case(Overflow::Saturate, "2mo", Ok(bounds().1.timestamp()))]
+  #[case(Overflow::Saturate, "-1000000y", Ok(bounds().0.timestamp()))]
+  #[case(Overflow::Wrap, "1mo", Ok(midnight(bounds().1.year(), 12, 15)))]
+  #[case(Overflow::Wrap, "2mo", Ok(midnight(bounds().0.year(), 3, 15)))]
+  fn month_policy(
+    #[case] overflow: Overflow,
+    #[case] add: &str,
+    #[case] expected: Result<i64, ()>,
+  ) {
+    let start = midnight(bounds().1.year(), 11, 15);
+    let dt = Zone::from(Tz::UTC).timestamp_opt(start, 0).unwrap();
+    let added = add_with(dt, &add.parse().unwrap(), overflow);
+    assert_eq!(added.map(|dt| dt.timestamp()).map_err(|_| ()), expected)
+  }
+
+  #[test]
+  fn wrap() {
+    let (min, max) = bounds();
+    let wrapped = add_with(
+      max.with_timezone(&Zone::from(Tz::UTC)),
+      &"1ns".parse().unwrap(),
+      Overflow::Wrap,
+    );
+    assert_eq!(wrapped, Ok(min.with_timezone(&Zone::from(Tz::UTC))));
   }
 }
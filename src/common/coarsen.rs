@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, TimeZone};
+use clap::Args;
+
+use super::Zone;
+use crate::hduration::HDuration;
+
+#[derive(Args)]
+pub struct CoarsenArgs {
+  /// Floor every printed time to a bucket of this width, aligned to the epoch,
+  /// and report to stderr how many distinct buckets remain
+  #[arg(long)]
+  coarsen: Option<HDuration>,
+}
+
+impl CoarsenArgs {
+  pub fn apply(&self, dt: DateTime<Zone>) -> Result<DateTime<Zone>, String> {
+    let Some(width) = &self.coarsen else {
+      return Ok(dt);
+    };
+    let nanos = width
+      .to_chrono()?
+      .num_nanoseconds()
+      .filter(|n| *n > 0)
+      .ok_or_else(|| format!("Coarsening width {} must be positive", width))?
+      as i128;
+    let stamp = dt.timestamp() as i128 * 1_000_000_000 + dt.timestamp_subsec_nanos() as i128;
+    let excess = stamp.rem_euclid(nanos) as i64;
+    dt.checked_sub_signed(Duration::nanoseconds(excess))
+      .ok_or_else(|| format!("Coarsening {} is out of range", dt))
+  }
+
+  /// How many distinct buckets the coarsened times fell into, if coarsening
+  pub fn summary<T: TimeZone>(&self, dts: &[DateTime<T>]) -> Option<String> {
+    let width = self.coarsen.as_ref()?;
+    let buckets = dts.iter().collect::<HashSet<_>>().len();
+    Some(format!(
+      "{} times in {} distinct {} buckets",
+      dts.len(),
+      buckets,
+      width
+    ))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::TimeZone;
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::{CoarsenArgs, Zone};
+
+  #[rstest]
+  #[case("1h", 1679258022, 1679256000)]
+  #[case("15m", 1679258022, 1679257800)]
+  #[case("1h", -1, -3600)]
+  fn apply(#[case] width: &str, #[case] stamp: i64, #[case] expected: i64) {
+    let args = CoarsenArgs {
+      coarsen: Some(width.parse().unwrap()),
+    };
+    let dt = Zone::from(Tz::UTC).timestamp_opt(stamp, 5).unwrap();
+    assert_eq!(
+      args
+        .apply(dt)
+        .map(|dt| (dt.timestamp(), dt.timestamp_subsec_nanos())),
+      Ok((expected, 0))
+    );
+  }
+}
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+use clap::ValueEnum;
+
+/// How to read a local time that DST repeats or skips
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Dst {
+  /// The first occurrence of a repeated time, and a skipped time moved back
+  /// by the length of the gap
+  Earliest,
+  /// The last occurrence of a repeated time, and a skipped time moved forward
+  /// by the length of the gap
+  Latest,
+  /// Refuse repeated and skipped times alike
+  Error,
+}
+
+/// Reads a local time in the zone. Without a policy, repeated times take their
+/// first occurrence and skipped times are an error
+pub fn from_local<T>(tz: &T, naive: &NaiveDateTime, dst: Option<Dst>) -> Result<DateTime<T>, String>
+where
+  T: TimeZone + Display,
+{
+  let shown = naive.format("%Y-%m-%dT%H:%M:%S");
+  match (tz.from_local_datetime(naive), dst) {
+    (LocalResult::Single(dt), _) => Ok(dt),
+    (LocalResult::Ambiguous(first, second), Some(Dst::Error)) => Err(format!(
+      "{} is ambiguous in {}, could be {} or {}",
+      shown,
+      tz,
+      first.with_timezone(&first.offset().fix()).to_rfc3339(),
+      second.with_timezone(&second.offset().fix()).to_rfc3339()
+    )),
+    (LocalResult::Ambiguous(_, second), Some(Dst::Latest)) => Ok(second),
+    (LocalResult::Ambiguous(first, _), _) => Ok(first),
+    (LocalResult::None, Some(dst @ (Dst::Earliest | Dst::Latest))) => {
+      // Reading the time with the offset from the other side of the gap lands
+      // the same distance beyond it
+      let day = Duration::days(1);
+      let side = if dst == Dst::Earliest {
+        *naive + day
+      } else {
+        *naive - day
+      };
+      let offset = tz.offset_from_utc_datetime(&side).fix();
+      Ok(tz.from_utc_datetime(&(*naive - offset)))
+    }
+    (LocalResult::None, _) => Err(format!("{} does not exist in {}", shown, tz)),
+  }
+}
+
+/// Reads a wall clock time moved from the given one back in its zone. A time
+/// skipped by DST on the new date moves forward past the gap
+pub fn relocal<T>(dt: &DateTime<T>, moved: &NaiveDateTime) -> Result<DateTime<T>, String>
+where
+  T: TimeZone + Display,
+{
+  let tz = dt.timezone();
+  from_local(&tz, moved, None).or_else(|_| from_local(&tz, moved, Some(Dst::Latest)))
+}
+
+#[cfg(test)]
+mod test {
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::{from_local, Dst};
+
+  #[rstest]
+  // Skipped when New York springs forward, and repeated when it falls back
+  #[case("2023-03-12T02:30:00", None, Err(()))]
+  #[case(
+    "2023-03-12T02:30:00",
+    Some(Dst::Earliest),
+    Ok("2023-03-12T01:30:00-05:00")
+  )]
+  #[case(
+    "2023-03-12T02:30:00",
+    Some(Dst::Latest),
+    Ok("2023-03-12T03:30:00-04:00")
+  )]
+  #[case("2023-03-12T02:30:00", Some(Dst::Error), Err(()))]
+  #[case("2023-11-05T01:30:00", None, Ok("2023-11-05T01:30:00-04:00"))]
+  #[case(
+    "2023-11-05T01:30:00",
+    Some(Dst::Earliest),
+    Ok("2023-11-05T01:30:00-04:00")
+  )]
+  #[case(
+    "2023-11-05T01:30:00",
+    Some(Dst::Latest),
+    Ok("2023-11-05T01:30:00-05:00")
+  )]
+  #[case("2023-11-05T01:30:00", Some(Dst::Error), Err(()))]
+  fn policy(#[case] naive: &str, #[case] dst: Option<Dst>, #[case] expected: Result<&str, ()>) {
+    let dt = from_local(&Tz::America__New_York, &naive.parse().unwrap(), dst);
+    assert_eq!(
+      dt.map(|dt| dt.to_rfc3339()).map_err(|_| ()),
+      expected.map(String::from)
+    )
+  }
+}
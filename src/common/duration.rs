@@ -0,0 +1,62 @@
+use clap::Args;
+
+use super::Precision;
+use crate::hduration::HDuration;
+
+#[derive(Args)]
+pub struct DurationArgs {
+  /// Print durations as a whole count of the given unit. Omitting will print
+  /// a human friendly duration
+  #[arg(value_enum, long = "duration", short = 'd')]
+  duration_precision: Option<Precision>,
+
+  /// Print fixed unit durations with this many decimal places rather than
+  /// truncating to a whole count
+  #[arg(long, requires = "duration_precision")]
+  decimal: Option<usize>,
+
+  /// The largest unit of human friendly durations, counting anything above it
+  /// in this unit instead
+  #[arg(value_enum, long, default_value_t = Precision::Weeks, conflicts_with = "duration_precision")]
+  largest: Precision,
+
+  /// The smallest unit of human friendly durations, dropping any remainder
+  /// below it
+  #[arg(value_enum, long, default_value_t = Precision::Nanos, conflicts_with = "duration_precision")]
+  smallest: Precision,
+}
+
+impl DurationArgs {
+  /// Whether durations are printed in a single fixed unit
+  pub fn is_fixed(&self) -> bool {
+    self.duration_precision.is_some()
+  }
+
+  /// Fails as format would, for commands that wait before printing durations
+  pub fn check(&self) -> Result<(), String> {
+    self.format(chrono::Duration::zero()).map(|_| ())
+  }
+
+  /// Renders the duration, failing when --largest is finer than --smallest
+  pub fn format(&self, d: chrono::Duration) -> Result<String, String> {
+    Ok(match (self.duration_precision, self.decimal) {
+      (None, _) => HDuration::from(d).between(self.largest, self.smallest)?,
+      (Some(p), Some(places)) => {
+        format!("{:.places$}", total_nanos(d) as f64 / p.nanos_per() as f64)
+      }
+      (Some(p @ (Precision::Millis | Precision::Micros | Precision::Nanos)), None) => {
+        (total_nanos(d) / p.nanos_per() as i128).to_string()
+      }
+      (Some(p), None) => (d.num_seconds() / p.seconds_per()).to_string(),
+    })
+  }
+}
+
+/// Durations past ~292 years overflow i64 nanoseconds, so widen first
+fn total_nanos(d: chrono::Duration) -> i128 {
+  let secs = d.num_seconds();
+  let subsec = (d - chrono::Duration::seconds(secs))
+    .num_nanoseconds()
+    .unwrap_or(0);
+  secs as i128 * 1_000_000_000 + subsec as i128
+}
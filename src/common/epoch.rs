@@ -0,0 +1,381 @@
+use std::{
+  env,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+
+use super::input::split_decimal;
+
+/// A custom encoding of instants, such as a proprietary epoch or BCD field
+pub trait EpochScheme {
+  /// Reads an encoded value into an instant
+  fn decode(&self, raw: &str) -> Result<DateTime<Utc>, String>;
+
+  /// Writes an instant in the scheme's encoding
+  fn encode(&self, dt: &DateTime<Utc>) -> Result<String, String>;
+}
+
+/// The schemes built in, by name
+const BUILT_IN: &[(&str, Linear)] = &[
+  (
+    "filetime",
+    // Windows FILETIME, in 100ns intervals since 1601-01-01
+    Linear {
+      epoch: -11_644_473_600,
+      unit: 100,
+      fractional: false,
+    },
+  ),
+  (
+    "ticks",
+    // .NET DateTime.Ticks, in 100ns intervals since 0001-01-01
+    Linear {
+      epoch: -62_135_596_800,
+      unit: 100,
+      fractional: false,
+    },
+  ),
+  (
+    "cocoa",
+    // Apple's reference date used by NSDate and Core Data, in seconds since
+    // 2001-01-01
+    Linear {
+      epoch: 978_307_200,
+      unit: 1_000_000_000,
+      fractional: true,
+    },
+  ),
+  (
+    "jd",
+    // Julian Date, in days since noon on 4713 BC January 1 of the Julian
+    // calendar
+    Linear {
+      epoch: -210_866_760_000,
+      unit: 86_400_000_000_000,
+      fractional: true,
+    },
+  ),
+  (
+    "jdn",
+    // Julian Day Number, the whole days of the Julian Date
+    Linear {
+      epoch: -210_866_760_000,
+      unit: 86_400_000_000_000,
+      fractional: false,
+    },
+  ),
+  (
+    "mjd",
+    // Modified Julian Date, in days since 1858-11-17
+    Linear {
+      epoch: -3_506_716_800,
+      unit: 86_400_000_000_000,
+      fractional: true,
+    },
+  ),
+];
+
+/// Finds the built in scheme with the given name. Inputs only ever reach
+/// these, so a stray name:value argument cannot run a program
+pub fn scheme(name: &str) -> Result<Box<dyn EpochScheme>, String> {
+  if let Some((_, linear)) = BUILT_IN.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+    return Ok(Box::new(*linear));
+  }
+  match name.to_ascii_lowercase().as_str() {
+    "gps" => Ok(Box::new(Gps { weeks: false })),
+    "gps-week" => Ok(Box::new(Gps { weeks: true })),
+    _ => Err(format!(
+      "Unknown epoch scheme {}, pass --epoch {} to run epc-epoch-{}",
+      name, name, name
+    )),
+  }
+}
+
+/// Finds the scheme with the given name, as explicitly asked for by --epoch.
+/// Anything not built in is looked up as an `epc-epoch-<name>` executable on
+/// the PATH
+pub fn named_scheme(name: &str) -> Result<Box<dyn EpochScheme>, String> {
+  if let Ok(built_in) = scheme(name) {
+    return Ok(built_in);
+  }
+  let program = format!("epc-epoch-{}", name);
+  env::var_os("PATH")
+    .iter()
+    .flat_map(env::split_paths)
+    .map(|dir| dir.join(&program))
+    .find(|p| p.is_file())
+    .map(|program| Box::new(External { program }) as Box<dyn EpochScheme>)
+    .ok_or_else(|| format!("Unknown epoch scheme {}, no {} on the PATH", name, program))
+}
+
+/// A count of fixed units since an epoch
+#[derive(Copy, Clone)]
+struct Linear {
+  /// The epoch, in seconds since 1970
+  epoch: i64,
+  /// The length of one unit, in nanoseconds
+  unit: i64,
+  /// Whether counts may be fractional, rather than whole
+  fractional: bool,
+}
+
+impl EpochScheme for Linear {
+  fn decode(&self, raw: &str) -> Result<DateTime<Utc>, String> {
+    let (whole, billionths) = split_decimal(raw)
+      .filter(|(_, billionths)| self.fractional || *billionths == 0)
+      .ok_or_else(|| match self.fractional {
+        true => format!("Expected a number but got {}", raw),
+        false => format!("Expected a whole number but got {}", raw),
+      })?;
+    let unit = self.unit as i128;
+    let nanos =
+      self.epoch as i128 * NANOS + whole as i128 * unit + billionths as i128 * unit / NANOS;
+    from_nanos(nanos).ok_or_else(|| format!("{} is out of range", raw))
+  }
+
+  fn encode(&self, dt: &DateTime<Utc>) -> Result<String, String> {
+    let since = to_nanos(dt) - self.epoch as i128 * NANOS;
+    Ok(count(since, self.unit as i128, self.fractional))
+  }
+}
+
+/// The start of GPS time, 1980-01-06, in seconds since 1970
+const GPS_EPOCH: i64 = 315_964_800;
+
+/// When each leap second took effect, in seconds since 1970, and how far GPS
+/// time has run ahead of UTC since
+const LEAP_SECONDS: &[(i64, i64)] = &[
+  (362_793_600, 1),
+  (394_329_600, 2),
+  (425_865_600, 3),
+  (489_024_000, 4),
+  (567_993_600, 5),
+  (631_152_000, 6),
+  (662_688_000, 7),
+  (709_948_800, 8),
+  (741_484_800, 9),
+  (773_020_800, 10),
+  (820_454_400, 11),
+  (867_715_200, 12),
+  (915_148_800, 13),
+  (1_136_073_600, 14),
+  (1_230_768_000, 15),
+  (1_341_100_800, 16),
+  (1_435_708_800, 17),
+  (1_483_228_800, 18),
+];
+
+/// Seconds of GPS time since its epoch, which unlike UTC counts every leap
+/// second. With weeks, written as week:seconds into the week
+#[derive(Copy, Clone)]
+struct Gps {
+  weeks: bool,
+}
+
+impl EpochScheme for Gps {
+  fn decode(&self, raw: &str) -> Result<DateTime<Utc>, String> {
+    let (week, secs) = match raw.split_once(':') {
+      Some((week, secs)) if self.weeks => (week.parse::<i64>().ok(), secs),
+      _ if self.weeks => (None, raw),
+      _ => (Some(0), raw),
+    };
+    let (whole, billionths) =
+      week
+        .and_then(|_| split_decimal(secs))
+        .ok_or_else(|| match self.weeks {
+          true => format!("Expected week:seconds but got {}", raw),
+          false => format!("Expected a number but got {}", raw),
+        })?;
+    let gps = GPS_EPOCH as i128 * NANOS
+      + week.unwrap_or(0) as i128 * WEEK
+      + whole as i128 * NANOS
+      + billionths as i128;
+    // Leap seconds are found by when they took effect in GPS time
+    let secs = gps.div_euclid(NANOS) as i64;
+    let ahead = LEAP_SECONDS
+      .iter()
+      .rev()
+      .find(|(at, ahead)| secs - ahead >= *at)
+      .map_or(0, |(_, ahead)| *ahead);
+    from_nanos(gps - ahead as i128 * NANOS).ok_or_else(|| format!("{} is out of range", raw))
+  }
+
+  fn encode(&self, dt: &DateTime<Utc>) -> Result<String, String> {
+    let ahead = LEAP_SECONDS
+      .iter()
+      .rev()
+      .find(|(at, _)| dt.timestamp() >= *at)
+      .map_or(0, |(_, ahead)| *ahead);
+    let since = to_nanos(dt) + (ahead - GPS_EPOCH) as i128 * NANOS;
+    Ok(match self.weeks {
+      true => format!(
+        "{}:{}",
+        since.div_euclid(WEEK),
+        count(since.rem_euclid(WEEK), NANOS, true)
+      ),
+      false => count(since, NANOS, true),
+    })
+  }
+}
+
+const NANOS: i128 = 1_000_000_000;
+const WEEK: i128 = 604_800 * NANOS;
+
+fn to_nanos(dt: &DateTime<Utc>) -> i128 {
+  dt.timestamp() as i128 * NANOS + dt.timestamp_subsec_nanos() as i128
+}
+
+fn from_nanos(nanos: i128) -> Option<DateTime<Utc>> {
+  let secs = i64::try_from(nanos.div_euclid(NANOS)).ok()?;
+  Utc
+    .timestamp_opt(secs, nanos.rem_euclid(NANOS) as u32)
+    .single()
+}
+
+/// Writes nanoseconds as a count of units, flooring unless it may be
+/// fractional
+fn count(nanos: i128, unit: i128, fractional: bool) -> String {
+  if !fractional || nanos % unit == 0 {
+    return nanos.div_euclid(unit).to_string();
+  }
+  let sign = if nanos < 0 { "-" } else { "" };
+  let fraction = format!("{:09}", (nanos.abs() % unit) * NANOS / unit);
+  format!(
+    "{}{}.{}",
+    sign,
+    nanos.abs() / unit,
+    fraction.trim_end_matches('0')
+  )
+}
+
+/// A scheme implemented by another program. It's run as `<program> decode
+/// <raw>` to print an RFC 3339 time, or `<program> encode <rfc3339>` to print
+/// the encoded value, exiting non-zero with a message on stderr if it can't
+pub struct External {
+  program: PathBuf,
+}
+
+impl External {
+  fn run(&self, action: &str, arg: &str) -> Result<String, String> {
+    let name = || {
+      self
+        .program
+        .file_name()
+        .map(Path::new)
+        .unwrap_or(&self.program)
+        .display()
+    };
+    let output = Command::new(&self.program)
+      .args([action, arg])
+      .output()
+      .map_err(|e| format!("Could not run {}: {}", name(), e))?;
+    if !output.status.success() {
+      let reason = String::from_utf8_lossy(&output.stderr);
+      return Err(format!(
+        "{} could not {} {}: {}",
+        name(),
+        action,
+        arg,
+        reason.trim()
+      ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+  }
+}
+
+impl EpochScheme for External {
+  fn decode(&self, raw: &str) -> Result<DateTime<Utc>, String> {
+    let out = self.run("decode", raw)?;
+    DateTime::parse_from_rfc3339(&out)
+      .map(|dt| dt.with_timezone(&Utc))
+      .map_err(|e| {
+        format!(
+          "Expected an RFC 3339 time from decode but got {}: {}",
+          out, e
+        )
+      })
+  }
+
+  fn encode(&self, dt: &DateTime<Utc>) -> Result<String, String> {
+    self.run("encode", &dt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{DateTime, Utc};
+  use rstest::*;
+
+  use super::{named_scheme, scheme, EpochScheme};
+
+  /// A scheme counting whole days since 2000-01-01
+  #[cfg(unix)]
+  const DAYS_SINCE_2000: &str = r#"#!/bin/sh
+case "$1" in
+  decode) date -u -d "2000-01-01 + $2 days" +%Y-%m-%dT%H:%M:%SZ ;;
+  encode) echo $(( ($(date -u -d "$2" +%s) - 946684800) / 86400 )) ;;
+esac
+"#;
+
+  // Runs a shell script, so only where one can be made executable
+  #[cfg(unix)]
+  #[test]
+  fn external() {
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    use chrono::TimeZone;
+
+    use super::External;
+
+    let dir = std::env::temp_dir().join(format!("epc-epoch-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let program = dir.join("epc-epoch-days2000");
+    fs::write(&program, DAYS_SINCE_2000).unwrap();
+    fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let scheme = External { program };
+    let dt = Utc.with_ymd_and_hms(2023, 3, 19, 0, 0, 0).unwrap();
+    assert_eq!(scheme.decode("8478"), Ok(dt));
+    assert_eq!(scheme.encode(&dt), Ok("8478".into()));
+    assert!(scheme.decode("soon").is_err());
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[rstest]
+  #[case("filetime", "133237316220000000", "2023-03-19T20:33:42Z")]
+  #[case("filetime", "0", "1601-01-01T00:00:00Z")]
+  #[case("ticks", "638148548221230000", "2023-03-19T20:33:42.123Z")]
+  #[case("cocoa", "700950822", "2023-03-19T20:33:42Z")]
+  #[case("cocoa", "700950822.25", "2023-03-19T20:33:42.250Z")]
+  #[case("cocoa", "-0.5", "2000-12-31T23:59:59.500Z")]
+  #[case("jd", "2460023.25", "2023-03-19T18:00:00Z")]
+  #[case("jdn", "2460023", "2023-03-19T12:00:00Z")]
+  #[case("mjd", "60388.5", "2024-03-19T12:00:00Z")]
+  #[case("gps", "1363293240", "2023-03-19T20:33:42Z")]
+  #[case("gps", "0.5", "1980-01-06T00:00:00.500Z")]
+  #[case("gps", "46828799", "1981-06-30T23:59:59Z")]
+  #[case("gps", "46828801", "1981-07-01T00:00:00Z")]
+  #[case("gps-week", "2254:74040.125", "2023-03-19T20:33:42.125Z")]
+  fn built_in(#[case] name: &str, #[case] raw: &str, #[case] rfc3339: &str) {
+    let scheme = scheme(name).unwrap();
+    let dt = DateTime::parse_from_rfc3339(rfc3339)
+      .unwrap()
+      .with_timezone(&Utc);
+    assert_eq!(scheme.decode(raw), Ok(dt));
+    assert_eq!(scheme.encode(&dt), Ok(raw.into()));
+  }
+
+  #[test]
+  fn unknown() {
+    assert_eq!(
+      scheme("nope").err(),
+      Some("Unknown epoch scheme nope, pass --epoch nope to run epc-epoch-nope".into())
+    );
+    assert_eq!(
+      named_scheme("nope").err(),
+      Some("Unknown epoch scheme nope, no epc-epoch-nope on the PATH".into())
+    )
+  }
+}
@@ -2,48 +2,241 @@ use std::{fmt::Display, str::FromStr};
 
 use chrono::{
   format::{Item, StrftimeItems},
-  DateTime, TimeZone,
+  DateTime, TimeZone, Utc,
 };
-use clap::Args;
+use clap::{Args, ValueEnum};
 
-use super::Precision;
+use super::{Precision, Rounding, StampPrecision, JS_MAX_SAFE};
+
+/// Named output formats, selected by passing @name as the format
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Preset {
+  /// RFC 3339 in the target timezone
+  Rfc3339,
+  /// AWS CloudTrail eventTime, in UTC to the second
+  Cloudtrail,
+  /// AWS CloudWatch Logs filter timestamps, in epoch milliseconds
+  Cloudwatch,
+  /// GCP Cloud Logging timestamps, in UTC to the nanosecond
+  Gcp,
+  /// A GCP Cloud Logging filter clause matching from the given time onwards
+  GcpFilter,
+  /// The IMF-fixdate of HTTP headers like Expires and Last-Modified, in GMT
+  Http,
+  /// The ISO 8601 week in the target timezone, such as 2023-W29
+  IsoWeek,
+  /// The ordinal date in the target timezone, such as 2023-196
+  Ordinal,
+}
+
+impl Preset {
+  fn format<T: TimeZone>(&self, dt: &DateTime<T>) -> String
+  where
+    T::Offset: Display,
+  {
+    let utc = dt.with_timezone(&Utc);
+    match self {
+      Preset::Rfc3339 => dt.to_rfc3339(),
+      Preset::Cloudtrail => utc.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+      Preset::Cloudwatch => utc.timestamp_millis().to_string(),
+      Preset::Gcp => utc.format("%Y-%m-%dT%H:%M:%S%.9fZ").to_string(),
+      Preset::GcpFilter => utc
+        .format("timestamp>=\"%Y-%m-%dT%H:%M:%S%.9fZ\"")
+        .to_string(),
+      Preset::Http => utc.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+      Preset::IsoWeek => dt.format("%G-W%V").to_string(),
+      Preset::Ordinal => dt.format("%Y-%j").to_string(),
+    }
+  }
+}
 
 #[derive(Clone)]
-struct Format(pub String);
+enum Format {
+  Strftime(String),
+  Preset(Preset),
+}
+
 impl FromStr for Format {
   type Err = String;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    if StrftimeItems::new(s).any(|v| matches!(v, Item::Error)) {
+    if let Some(name) = s.strip_prefix('@') {
+      let names = || {
+        Preset::value_variants()
+          .iter()
+          .filter_map(|p| p.to_possible_value())
+          .map(|p| format!("@{}", p.get_name()))
+          .collect::<Vec<_>>()
+          .join(", ")
+      };
+      Preset::from_str(name, true)
+        .map(Format::Preset)
+        .map_err(|_| format!("unknown preset, expected one of {}", names()))
+    } else if StrftimeItems::new(s).any(|v| matches!(v, Item::Error)) {
       Err("contains unknown specifier".into())
     } else {
-      Ok(Format(s.into()))
+      Ok(Format::Strftime(s.into()))
     }
   }
 }
 
+/// The format -f uses when given without a value
+pub const DEFAULT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%z";
+
 #[derive(Args)]
 pub struct FormatArgs {
   /// What format to print the date strings in. Omitting will retain timestamps.
   ///
   /// Valid specifiers can be found at https://docs.rs/chrono/latest/chrono/format/strftime/index.html
-  /// A reasonable default has been given, allowing you to pass -f alone
-  #[arg(long, short = 'f', default_missing_value = "%Y-%m-%dT%H:%M:%S%z", require_equals=true, num_args=0..=1)]
+  /// A reasonable default has been given, allowing you to pass -f alone.
+  /// Presets are also available: @rfc3339, @cloudtrail, @cloudwatch, @gcp, @gcp-filter, @http, @iso-week, @ordinal
+  #[arg(long, short = 'f', default_missing_value = DEFAULT_FORMAT, require_equals=true, num_args=0..=1, env = "EPC_FORMAT")]
   output_format: Option<Format>,
 
+  /// Print each date relative to now, such as "3 days ago" or "in 45 minutes".
+  /// Takes precedence over -f, so it also overrides a format set by
+  /// $EPC_FORMAT or the config file
+  #[arg(long, short = 'r')]
+  relative: bool,
+
   /// What precision timestamps should be treated as
-  #[arg(value_enum, long, short, default_value_t=Precision::Millis)]
-  pub precision: Precision,
+  #[arg(value_enum, long, short, default_value_t=StampPrecision::Fixed(Precision::Millis), env = "EPC_PRECISION")]
+  pub precision: StampPrecision,
+
+  /// How stamps printed coarser than their input drop the extra digits
+  #[arg(value_enum, long, default_value_t = Rounding::Truncate)]
+  subsec_rounding: Rounding,
+
+  /// Flag any printed stamp too large for a JavaScript Number to hold
+  /// exactly, suggesting a coarser precision that fits
+  #[arg(long)]
+  js_safe: bool,
 }
 
 impl FormatArgs {
-  pub fn format<T: TimeZone>(&self, dt: &DateTime<T>) -> String
+  pub fn format<T: TimeZone>(&self, dt: &DateTime<T>) -> Result<String, String>
   where
     T::Offset: Display,
   {
-    match &self.output_format {
-      Some(fmt) => dt.format(&fmt.0).to_string(),
-      None => self.precision.as_stamp(dt).to_string(),
+    Ok(match &self.output_format {
+      _ if self.relative => relative(dt, &Utc::now()),
+      Some(Format::Strftime(fmt)) => dt.format(fmt).to_string(),
+      Some(Format::Preset(preset)) => preset.format(dt),
+      None => self
+        .precision
+        .output()
+        .as_rounded_stamp(dt, self.subsec_rounding)?
+        .to_string(),
+    })
+  }
+
+  /// Whether dates print as epoch stamps, which read the same in every zone
+  pub fn retains_stamps(&self) -> bool {
+    self.output_format.is_none() && !self.relative
+  }
+
+  /// With --js-safe, explains why the stamp printed for the date would lose
+  /// precision as a JavaScript Number
+  pub fn js_unsafe<T: TimeZone>(&self, dt: &DateTime<T>) -> Option<String> {
+    if !self.js_safe || !self.retains_stamps() {
+      return None;
     }
+    let pre = self.precision.output();
+    let stamp = pre.as_rounded_stamp(dt, self.subsec_rounding).ok()?;
+    if stamp.unsigned_abs() <= JS_MAX_SAFE as u64 {
+      return None;
+    }
+    let suggestion = Precision::STAMPED
+      .iter()
+      .rev()
+      .filter(|p| **p < pre)
+      .find(|p| {
+        p.as_stamp(dt)
+          .is_ok_and(|s| s.unsigned_abs() <= JS_MAX_SAFE as u64)
+      })
+      .and_then(|p| p.to_possible_value())
+      .map(|p| format!(", try -p {}", p.get_name()))
+      .unwrap_or_default();
+    Some(format!(
+      "{} exceeds 2^53-1 and is not safe as a JavaScript Number{}",
+      stamp, suggestion
+    ))
+  }
+}
+
+const RELATIVE_UNITS: [(&str, i64); 7] = [
+  ("year", 365 * 86400),
+  ("month", 30 * 86400),
+  ("week", 7 * 86400),
+  ("day", 86400),
+  ("hour", 3600),
+  ("minute", 60),
+  ("second", 1),
+];
+
+/// Describes the distance of the given date from now using the largest whole unit
+pub fn relative<T: TimeZone>(dt: &DateTime<T>, now: &DateTime<Utc>) -> String {
+  let secs = dt.timestamp() - now.timestamp();
+  let Some((unit, count)) = RELATIVE_UNITS
+    .iter()
+    .find(|(_, per)| secs.abs() >= *per)
+    .map(|(unit, per)| (unit, secs.abs() / per))
+  else {
+    return "now".into();
+  };
+  let plural = if count == 1 { "" } else { "s" };
+  if secs < 0 {
+    format!("{} {}{} ago", count, unit, plural)
+  } else {
+    format!("in {} {}{}", count, unit, plural)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{TimeZone, Utc};
+  use rstest::*;
+
+  use super::{relative, Format, Preset};
+
+  #[rstest]
+  #[case(0, "now")]
+  #[case(-1, "1 second ago")]
+  #[case(45 * 60, "in 45 minutes")]
+  #[case(-2 * 3600 - 59, "2 hours ago")]
+  #[case(-3 * 86400, "3 days ago")]
+  #[case(14 * 86400, "in 2 weeks")]
+  #[case(-400 * 86400, "1 year ago")]
+  fn relative_to_now(#[case] offset: i64, #[case] expected: &str) {
+    let now = Utc.timestamp_opt(1679258022, 0).unwrap();
+    let dt = Utc.timestamp_opt(1679258022 + offset, 0).unwrap();
+    assert_eq!(relative(&dt, &now), expected)
+  }
+
+  #[rstest]
+  #[case("@rfc3339", "2023-03-19T16:33:42.123456789-04:00")]
+  #[case("@cloudtrail", "2023-03-19T20:33:42Z")]
+  #[case("@cloudwatch", "1679258022123")]
+  #[case("@GCP", "2023-03-19T20:33:42.123456789Z")]
+  #[case("@gcp-filter", "timestamp>=\"2023-03-19T20:33:42.123456789Z\"")]
+  #[case("@http", "Sun, 19 Mar 2023 20:33:42 GMT")]
+  #[case("@iso-week", "2023-W11")]
+  #[case("@ordinal", "2023-078")]
+  fn presets(#[case] input: &str, #[case] expected: &str) {
+    let dt = chrono::DateTime::parse_from_rfc3339("2023-03-19T16:33:42.123456789-04:00").unwrap();
+    let formatted = match input.parse::<Format>() {
+      Ok(Format::Preset(p)) => p.format(&dt),
+      _ => panic!("Not a preset"),
+    };
+    assert_eq!(formatted, expected)
+  }
+
+  #[test]
+  fn unknown_preset() {
+    assert!("@nope".parse::<Format>().is_err());
+    assert!(matches!(
+      "@cloudtrail".parse::<Format>(),
+      Ok(Format::Preset(Preset::Cloudtrail))
+    ));
   }
 }
@@ -0,0 +1,165 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use chrono::{Datelike, Month, NaiveDate, Weekday};
+use clap::Args;
+
+/// One line of a holidays file
+#[derive(Clone, Debug, PartialEq)]
+enum Rule {
+  /// A single date, such as 2023-12-26
+  Date(NaiveDate),
+  /// The same date every year, such as 12-25
+  Yearly(u32, u32),
+  /// The nth weekday of a month every year, such as 4th thu of nov. The last
+  /// one of the month is n = 0
+  Nth(u32, Weekday, u32),
+}
+
+impl FromStr for Rule {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+      return Ok(Rule::Date(date));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&format!("2000-{}", s), "%Y-%m-%d") {
+      return Ok(Rule::Yearly(date.month(), date.day()));
+    }
+    let invalid = || {
+      format!(
+        "Expected YYYY-MM-DD, MM-DD, or 4th thu of nov but got: {}",
+        s
+      )
+    };
+    let words = s.split_whitespace().collect::<Vec<_>>();
+    let [nth, weekday, "of", month] = words[..] else {
+      return Err(invalid());
+    };
+    let n = match nth {
+      "last" => 0,
+      "1st" => 1,
+      "2nd" => 2,
+      "3rd" => 3,
+      "4th" => 4,
+      "5th" => 5,
+      _ => return Err(invalid()),
+    };
+    let weekday = weekday.parse::<Weekday>().map_err(|_| invalid())?;
+    let month = month.parse::<Month>().map_err(|_| invalid())?;
+    Ok(Rule::Nth(n, weekday, month.number_from_month()))
+  }
+}
+
+impl Rule {
+  fn matches(&self, date: NaiveDate) -> bool {
+    match *self {
+      Rule::Date(d) => d == date,
+      Rule::Yearly(month, day) => date.month() == month && date.day() == day,
+      Rule::Nth(n, weekday, month) => {
+        let is_last = || {
+          date
+            .checked_add_signed(chrono::Duration::weeks(1))
+            .is_none_or(|next| next.month() != month)
+        };
+        date.month() == month
+          && date.weekday() == weekday
+          && match n {
+            0 => is_last(),
+            n => (date.day() - 1) / 7 + 1 == n,
+          }
+      }
+    }
+  }
+}
+
+/// The dates that are not business days
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Holidays(Vec<Rule>);
+
+impl FromStr for Holidays {
+  type Err = String;
+
+  /// Reads a holidays file of one rule per line, ignoring blank lines and
+  /// anything after a #
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.lines()
+      .map(|line| line.split('#').next().unwrap_or("").trim())
+      .filter(|line| !line.is_empty())
+      .map(Rule::from_str)
+      .collect::<Result<_, _>>()
+      .map(Holidays)
+  }
+}
+
+impl Holidays {
+  pub fn contains(&self, date: NaiveDate) -> bool {
+    self.0.iter().any(|rule| rule.matches(date))
+  }
+}
+
+#[derive(Args)]
+pub struct HolidayArgs {
+  /// A date that is not a business day, in YYYY-MM-DD form. Repeat for each
+  /// holiday
+  #[arg(long)]
+  holiday: Vec<NaiveDate>,
+
+  /// A file of holidays, one per line as a date (2023-12-26), a yearly date
+  /// (12-25), or a yearly weekday (4th thu of nov, last mon of may)
+  #[arg(long, env = "EPC_HOLIDAYS")]
+  holidays: Option<PathBuf>,
+}
+
+impl HolidayArgs {
+  /// The given holidays, reading the file only once they are needed
+  pub fn load(&self) -> Result<Holidays, String> {
+    let mut holidays = match &self.holidays {
+      None => Holidays::default(),
+      Some(path) => fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?
+        .parse()
+        .map_err(|e| format!("Invalid holidays {}: {}", path.display(), e))?,
+    };
+    holidays
+      .0
+      .extend(self.holiday.iter().copied().map(Rule::Date));
+    Ok(holidays)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use indoc::indoc;
+  use rstest::*;
+
+  use super::Holidays;
+
+  #[rstest]
+  #[case("2023-12-26", true)]
+  #[case("2024-12-26", false)]
+  #[case("2031-12-25", true)]
+  #[case("2023-11-23", true)]
+  #[case("2023-11-16", false)]
+  #[case("2023-05-29", true)]
+  #[case("2023-05-22", false)]
+  fn contains(#[case] date: &str, #[case] expected: bool) {
+    let holidays = indoc! {"
+      # Boxing day, just this once
+      2023-12-26
+      12-25
+      4th thu of nov
+      last mon of may  # Memorial day
+    "}
+    .parse::<Holidays>()
+    .unwrap();
+    assert_eq!(holidays.contains(date.parse().unwrap()), expected)
+  }
+
+  #[rstest]
+  #[case("13-01")]
+  #[case("6th thu of nov")]
+  #[case("last thu in nov")]
+  fn invalid(#[case] line: &str) {
+    assert!(line.parse::<Holidays>().is_err())
+  }
+}
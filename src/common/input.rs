@@ -0,0 +1,391 @@
+use std::{
+  io::{self, BufRead},
+  str::FromStr,
+};
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Offset, Utc};
+
+use super::{
+  dst::from_local, epoch::scheme, timezone::resolve_place, Dst, Precision, RelativeInput,
+  StampPrecision, Zone,
+};
+
+#[derive(Clone)]
+pub enum ConversionInput {
+  Now,
+  Stamp(i64),
+  /// A stamp with billionths of its precision as the fractional part
+  Fractional(i64, u32),
+  String(DateTime<FixedOffset>),
+  /// A date or date-time without an offset, read in the target timezone
+  Local(NaiveDateTime),
+  /// A syslog style time without a year, as in Mar 19 16:36:26, read as the
+  /// latest such time in the target timezone that is not after now
+  Yearless(NaiveDateTime),
+  Relative(RelativeInput),
+  /// Another input suffixed with the zone it belongs to, as in
+  /// 2025-03-30T02:30+01:00[Europe/Berlin]
+  Zoned(Box<ConversionInput>, Zone),
+  /// A value in a built in epoch scheme, given as name:value
+  Scheme {
+    name: String,
+    raw: String,
+  },
+}
+
+impl ConversionInput {
+  /// The zone the input was explicitly suffixed with, if any
+  pub fn zone(&self) -> Option<Zone> {
+    match self {
+      ConversionInput::Zoned(_, zone) => Some(*zone),
+      _ => None,
+    }
+  }
+
+  /// The given inputs, or one read from each non-blank line of stdin when
+  /// there are none
+  pub fn or_stdin(inputs: &[ConversionInput]) -> Result<Vec<ConversionInput>, String> {
+    if !inputs.is_empty() {
+      return Ok(inputs.to_vec());
+    }
+    io::stdin()
+      .lock()
+      .lines()
+      .map(|l| l.map_err(|e| format!("Could not read stdin: {}", e)))
+      .filter(|l| l.as_ref().map_or(true, |l| !l.trim().is_empty()))
+      .map(|l| l.and_then(|l| l.trim().parse::<ConversionInput>()))
+      .collect()
+  }
+
+  /// Resolves the input into a datetime, where `now` anchors relative inputs
+  pub fn to_dt(
+    &self,
+    precision: &StampPrecision,
+    now: &DateTime<Zone>,
+  ) -> Result<DateTime<FixedOffset>, String> {
+    self.to_dt_with(precision, now, None)
+  }
+
+  /// Resolves the input like `to_dt`, reading local times DST repeats or skips
+  /// by the given policy
+  pub fn to_dt_with(
+    &self,
+    precision: &StampPrecision,
+    now: &DateTime<Zone>,
+    dst: Option<Dst>,
+  ) -> Result<DateTime<FixedOffset>, String> {
+    match self {
+      ConversionInput::Now => Ok(now.with_timezone(&now.offset().fix())),
+      ConversionInput::String(dt) => Ok(*dt),
+      ConversionInput::Relative(rel) => rel.resolve(now),
+      ConversionInput::Zoned(inner, zone) => inner
+        .to_dt_with(precision, &now.with_timezone(zone), dst)
+        .map(|dt| dt.with_timezone(zone))
+        .map(|dt| dt.with_timezone(&dt.offset().fix())),
+      ConversionInput::Local(naive) => {
+        from_local(&now.timezone(), naive, dst).map(|dt| dt.with_timezone(&dt.offset().fix()))
+      }
+      ConversionInput::Yearless(naive) => {
+        let year = now.year();
+        let in_year = |year| {
+          naive
+            .with_year(year)
+            .ok_or_else(|| format!("{} does not occur in {}", naive.format("%b %e"), year))
+            .and_then(|n| ConversionInput::Local(n).to_dt_with(precision, now, dst))
+        };
+        match in_year(year) {
+          Ok(dt) if dt <= *now => Ok(dt),
+          this_year => in_year(year - 1).or(this_year),
+        }
+      }
+      ConversionInput::Stamp(ts) => precision
+        .of(*ts)
+        .parse(*ts)
+        .single()
+        .map(|dt| dt.into())
+        .ok_or_else(|| format!("Could not parse: {}", ts)),
+      ConversionInput::Scheme { name, raw } => scheme(name)?
+        .decode(raw)
+        .map(|dt| dt.with_timezone(&Utc.fix())),
+      ConversionInput::Fractional(whole, frac) => precision
+        .of(*whole)
+        .parse_fractional(*whole, *frac)
+        .single()
+        .map(|dt| dt.into())
+        .ok_or_else(|| format!("Could not parse: {}.{:09}", whole, frac)),
+    }
+  }
+}
+
+impl FromStr for ConversionInput {
+  type Err = String;
+
+  fn from_str(arg: &str) -> Result<Self, Self::Err> {
+    if arg.eq_ignore_ascii_case("now") {
+      return Ok(ConversionInput::Now);
+    }
+    if let Some(iso) = with_t(arg) {
+      return iso.parse().map_err(|_| format!("Could not parse: {}", arg));
+    }
+    let zoned = arg.strip_suffix(']').and_then(|a| a.split_once('['));
+    if let Some((inner, zone)) = zoned.filter(|(inner, _)| !inner.is_empty()) {
+      let zone = zone.parse::<Zone>()?;
+      return Ok(ConversionInput::Zoned(Box::new(inner.parse()?), zone));
+    }
+    if let Ok(dt) = DateTime::parse_from_str(arg, "%s %z") {
+      // As git records commit times, in seconds alongside the author's offset
+      let utc = ConversionInput::String(dt.with_timezone(&Utc.fix()));
+      return Ok(ConversionInput::Zoned(
+        Box::new(utc),
+        Zone::Fixed(*dt.offset()),
+      ));
+    }
+    if let Some(secs) = arg.strip_prefix('@') {
+      return split_decimal(secs)
+        .and_then(|(whole, frac)| Precision::Secs.parse_fractional(whole, frac).single())
+        .map(|dt| ConversionInput::String(dt.into()))
+        .ok_or_else(|| format!("Could not parse: {}", arg));
+    }
+    if let Ok(ts) = arg.parse::<i64>() {
+      return Ok(ConversionInput::Stamp(ts));
+    }
+    if let Some((whole, frac)) = split_decimal(arg) {
+      return Ok(ConversionInput::Fractional(whole, frac));
+    }
+    if let Ok(dt) = arg.parse::<DateTime<FixedOffset>>() {
+      return Ok(ConversionInput::String(dt));
+    }
+    if let Ok(dt) = parse_clf(arg) {
+      return Ok(ConversionInput::String(dt));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(arg) {
+      return Ok(ConversionInput::String(dt));
+    }
+    if let Ok(naive) = arg.parse::<NaiveDateTime>() {
+      return Ok(ConversionInput::Local(naive));
+    }
+    if let Some(with_secs) = with_seconds(arg) {
+      if let Ok(dt) = with_secs.parse::<DateTime<FixedOffset>>() {
+        return Ok(ConversionInput::String(dt));
+      }
+      if let Ok(naive) = with_secs.parse::<NaiveDateTime>() {
+        return Ok(ConversionInput::Local(naive));
+      }
+    }
+    if let Some(midnight) = arg
+      .parse::<NaiveDate>()
+      .ok()
+      .and_then(|d| d.and_hms_opt(0, 0, 0))
+    {
+      return Ok(ConversionInput::Local(midnight));
+    }
+    if let Some(date) = parse_date_output(arg) {
+      return date;
+    }
+    if let Ok(naive) = parse_syslog(arg) {
+      return Ok(ConversionInput::Yearless(naive));
+    }
+    let named = arg.split_once(':').filter(|(name, raw)| {
+      name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+          .chars()
+          .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !raw.is_empty()
+    });
+    if let Some((name, raw)) = named {
+      return Ok(ConversionInput::Scheme {
+        name: name.into(),
+        raw: raw.into(),
+      });
+    }
+    arg
+      .parse::<RelativeInput>()
+      .map(ConversionInput::Relative)
+      .map_err(|_| format!("Could not parse: {}", arg))
+  }
+}
+
+/// Swaps the space some logs put between an ISO 8601 date and time for a T,
+/// along with any comma before the fraction of a second
+fn with_t(s: &str) -> Option<String> {
+  let (date, time) = (s.get(..10)?, s.get(11..)?);
+  if s.as_bytes()[10] != b' ' || date.parse::<NaiveDate>().is_err() {
+    return None;
+  }
+  let time = match time.get(8..9) {
+    Some(",") => format!("{}.{}", &time[..8], &time[9..]),
+    _ => time.to_string(),
+  };
+  Some(format!("{}T{}", date, time))
+}
+
+/// Parses the output of the date command, as in Sat Jul 15 14:30:45 EDT 2023,
+/// reading the zone abbreviation like -t would. Without a zone, as asctime
+/// writes, the time is read in the target timezone
+fn parse_date_output(s: &str) -> Option<Result<ConversionInput, String>> {
+  let tokens = s.split_whitespace().collect::<Vec<_>>();
+  let (zone, rest) = match tokens.as_slice() {
+    [wd, mon, day, time, zone, year] => (Some(*zone), [*wd, mon, day, time, year]),
+    [wd, mon, day, time, year] => (None, [*wd, mon, day, time, year]),
+    _ => return None,
+  };
+  let naive = NaiveDateTime::parse_from_str(&rest.join(" "), "%a %b %e %H:%M:%S %Y").ok()?;
+  let local = ConversionInput::Local(naive);
+  Some(match zone {
+    None => Ok(local),
+    Some(zone) => resolve_place(zone).map(|tz| ConversionInput::Zoned(Box::new(local), tz.into())),
+  })
+}
+
+/// Parses an RFC 3164 syslog time, which has no year, in the leap year 2000 so
+/// that Feb 29 is kept until the year is known
+fn parse_syslog(s: &str) -> chrono::ParseResult<NaiveDateTime> {
+  NaiveDateTime::parse_from_str(&format!("2000 {}", s), "%Y %b %e %H:%M:%S")
+}
+
+/// Fills in the seconds of an ISO 8601 date-time given only to the minute,
+/// such as 2025-03-30T02:30+01:00
+fn with_seconds(s: &str) -> Option<String> {
+  let (minutes, rest) = (s.get(..16)?, s.get(16..)?);
+  let bytes = minutes.as_bytes();
+  if bytes[10] != b'T' || bytes[13] != b':' || rest.starts_with(':') {
+    return None;
+  }
+  Some(format!("{}:00{}", minutes, rest))
+}
+
+/// The format Apache and nginx access logs stamp requests with
+pub const CLF_FORMAT: &str = "%d/%b/%Y:%H:%M:%S %z";
+
+/// Parses a Common Log Format time, with or without its surrounding brackets
+fn parse_clf(s: &str) -> chrono::ParseResult<DateTime<FixedOffset>> {
+  let s = s
+    .strip_prefix('[')
+    .and_then(|s| s.strip_suffix(']'))
+    .unwrap_or(s);
+  DateTime::parse_from_str(s, CLF_FORMAT)
+}
+
+/// Splits a decimal like "-12.5" into whole units and billionths of the next
+/// unit, flooring so the fractional part is never negative
+pub(super) fn split_decimal(s: &str) -> Option<(i64, u32)> {
+  let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+  if frac.len() > 9 || !frac.chars().all(|c| c.is_ascii_digit()) {
+    return None;
+  }
+  let whole = whole.parse::<i64>().ok()?;
+  let frac = format!("{:0<9}", frac).parse::<u32>().ok()?;
+  if s.starts_with('-') && frac > 0 {
+    Some((whole.checked_sub(1)?, 1_000_000_000 - frac))
+  } else {
+    Some((whole, frac))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::{split_decimal, ConversionInput, Zone};
+  use crate::common::Precision;
+
+  #[rstest]
+  #[case("12", Some((12, 0)))]
+  #[case("12.5", Some((12, 500_000_000)))]
+  #[case("-12.5", Some((-13, 500_000_000)))]
+  #[case("-0.000000001", Some((-1, 999_999_999)))]
+  #[case("1.0000000001", None)]
+  #[case("1.-5", None)]
+  fn decimal(#[case] input: &str, #[case] expected: Option<(i64, u32)>) {
+    assert_eq!(split_decimal(input), expected)
+  }
+
+  #[rstest]
+  #[case("@1679258022", 1679258022000000000)]
+  #[case("@1679258022.123", 1679258022123000000)]
+  #[case("@-1.5", -1500000000)]
+  fn gnu_epoch(#[case] input: &str, #[case] expected_nanos: i64) {
+    let parsed = input.parse::<ConversionInput>();
+    let nanos = match parsed {
+      Ok(ConversionInput::String(dt)) => Precision::Nanos.as_stamp(&dt).ok(),
+      _ => None,
+    };
+    assert_eq!(nanos, Some(expected_nanos))
+  }
+
+  #[rstest]
+  #[case("19/Mar/2023:16:33:42 -0400", Some("2023-03-19T16:33:42-04:00"))]
+  #[case("[19/Mar/2023:16:33:42 +0000]", Some("2023-03-19T16:33:42+00:00"))]
+  #[case("[19/Mar/2023:16:33:42]", None)]
+  fn clf(#[case] input: &str, #[case] expected: Option<&str>) {
+    let parsed = input.parse::<ConversionInput>();
+    let dt = match parsed {
+      Ok(ConversionInput::String(dt)) => Some(dt.to_rfc3339()),
+      _ => None,
+    };
+    assert_eq!(dt.as_deref(), expected)
+  }
+
+  #[rstest]
+  #[case("Sun, 19 Mar 2023 16:36:26 -0400", Some("2023-03-19T16:36:26-04:00"))]
+  #[case("19 Mar 2023 16:36:26 +0000", Some("2023-03-19T16:36:26+00:00"))]
+  #[case("Tue, 29 Apr 2025 10:00:00 GMT", Some("2025-04-29T10:00:00+00:00"))]
+  #[case("Mon, 19 Mar 2023 16:36:26 -0400", None)]
+  fn rfc2822(#[case] input: &str, #[case] expected: Option<&str>) {
+    let parsed = input.parse::<ConversionInput>();
+    let dt = match parsed {
+      Ok(ConversionInput::String(dt)) => Some(dt.to_rfc3339()),
+      _ => None,
+    };
+    assert_eq!(dt.as_deref(), expected)
+  }
+
+  #[rstest]
+  #[case("2023-03-19", "2023-03-19T00:00:00-04:00")]
+  #[case("2023-03-19T16:33:42", "2023-03-19T16:33:42-04:00")]
+  #[case("2023-03-19 16:33:42", "2023-03-19T16:33:42-04:00")]
+  #[case("2023-03-19 16:33:42,5", "2023-03-19T16:33:42.500-04:00")]
+  #[case("Sat Jul 15 14:30:45 UTC 2023", "2023-07-15T14:30:45+00:00")]
+  #[case("Sat Jul 15 14:30:45 EDT 2023", "2023-07-15T14:30:45-04:00")]
+  #[case("Wed Jul  5 14:30:45 2023", "2023-07-05T14:30:45-04:00")]
+  // Syslog times take the latest year that keeps them in the past
+  #[case("Mar 19 16:00:00", "2023-03-19T16:00:00-04:00")]
+  #[case("Mar 19 17:00:00", "2022-03-19T17:00:00-04:00")]
+  #[case("Dec  1 09:00:00", "2022-12-01T09:00:00-05:00")]
+  // Skipped by the spring forward
+  #[case(
+    "2023-03-12T02:30:00",
+    "2023-03-12T02:30:00 does not exist in America/New_York"
+  )]
+  fn local(#[case] input: &str, #[case] expected: &str) {
+    use chrono::TimeZone;
+    let now = Zone::from(chrono_tz::Tz::America__New_York)
+      .timestamp_opt(1679258022, 0)
+      .unwrap();
+    let resolved = input
+      .parse::<ConversionInput>()
+      .and_then(|i| i.to_dt(&crate::common::StampPrecision::Auto, &now));
+    assert_eq!(
+      resolved.map(|dt| dt.to_rfc3339()).unwrap_or_else(|e| e),
+      expected
+    )
+  }
+
+  #[rstest]
+  #[case("2025-03-30T02:30+01:00", "2025-03-30T02:30:00+01:00")]
+  #[case("2025-03-30T02:30Z", "2025-03-30T02:30:00+00:00")]
+  #[case("2025-03-30T02:30+01:00[Europe/Berlin]", "2025-03-30T03:30:00+02:00")]
+  #[case("2025-03-30T12:00[Asia/Tokyo]", "2025-03-30T12:00:00+09:00")]
+  #[case("1743301800[utc]", "2025-03-30T02:30:00+00:00")]
+  #[case("1679258022 -0400", "2023-03-19T16:33:42-04:00")]
+  fn zoned(#[case] input: &str, #[case] expected: &str) {
+    use chrono::TimeZone;
+    let now = Zone::from(chrono_tz::Tz::America__New_York)
+      .timestamp_opt(1679258022, 0)
+      .unwrap();
+    let resolved = input
+      .parse::<ConversionInput>()
+      .and_then(|i| i.to_dt(&crate::common::StampPrecision::Auto, &now));
+    assert_eq!(resolved.map(|dt| dt.to_rfc3339()), Ok(expected.into()))
+  }
+}
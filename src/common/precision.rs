@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
-use clap::ValueEnum;
+use clap::{builder::PossibleValue, ValueEnum};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Precision {
@@ -17,6 +17,8 @@ pub enum Precision {
   Secs,
   /// Milliseconds
   Millis,
+  /// Microseconds
+  Micros,
   /// Nanoseconds
   Nanos,
 }
@@ -32,6 +34,7 @@ impl FromStr for Precision {
       "m" | "mins" => Precision::Mins,
       "s" | "secs" => Precision::Secs,
       "ms" | "millis" => Precision::Millis,
+      "us" | "µs" | "μs" | "micros" => Precision::Micros,
       "ns" | "nanos" => Precision::Nanos,
       _ => return Err(format!("Unknown precision: {}", s)),
     };
@@ -39,7 +42,89 @@ impl FromStr for Precision {
   }
 }
 
+/// The largest integer a JavaScript Number holds exactly, 2^53-1
+pub const JS_MAX_SAFE: i64 = (1 << 53) - 1;
+
+/// How stamps drop the digits finer than their precision
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Rounding {
+  /// Drop them, moving towards the past
+  Truncate,
+  /// Round to the nearest stamp, breaking ties towards the even one
+  HalfEven,
+}
+
+/// The precision stamps are given in, which may instead be inferred per stamp
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StampPrecision {
+  Auto,
+  Fixed(Precision),
+}
+
+impl ValueEnum for StampPrecision {
+  fn value_variants<'a>() -> &'a [Self] {
+    &[
+      StampPrecision::Auto,
+      StampPrecision::Fixed(Precision::Weeks),
+      StampPrecision::Fixed(Precision::Days),
+      StampPrecision::Fixed(Precision::Hours),
+      StampPrecision::Fixed(Precision::Mins),
+      StampPrecision::Fixed(Precision::Secs),
+      StampPrecision::Fixed(Precision::Millis),
+      StampPrecision::Fixed(Precision::Micros),
+      StampPrecision::Fixed(Precision::Nanos),
+    ]
+  }
+
+  fn to_possible_value(&self) -> Option<PossibleValue> {
+    match self {
+      StampPrecision::Auto => Some(
+        PossibleValue::new("auto")
+          .help("Infer seconds, millis, micros, or nanos from each stamp's magnitude"),
+      ),
+      StampPrecision::Fixed(p) => p.to_possible_value(),
+    }
+  }
+}
+
+impl StampPrecision {
+  /// The precision the given stamp should be read in
+  pub fn of(&self, ts: i64) -> Precision {
+    match self {
+      StampPrecision::Auto => Precision::infer(ts),
+      StampPrecision::Fixed(p) => *p,
+    }
+  }
+
+  /// The precision stamps are printed in, which is millis when inferring
+  pub fn output(&self) -> Precision {
+    match self {
+      StampPrecision::Auto => Precision::Millis,
+      StampPrecision::Fixed(p) => *p,
+    }
+  }
+}
+
 impl Precision {
+  /// The precisions a bare stamp is plausibly written in
+  pub const STAMPED: [Precision; 4] = [
+    Precision::Secs,
+    Precision::Millis,
+    Precision::Micros,
+    Precision::Nanos,
+  ];
+
+  /// Guesses the precision of a stamp from its magnitude, assuming it lands
+  /// within a few thousand years of 1970
+  pub fn infer(ts: i64) -> Self {
+    match ts.unsigned_abs() {
+      0..=99_999_999_999 => Precision::Secs,
+      100_000_000_000..=99_999_999_999_999 => Precision::Millis,
+      100_000_000_000_000..=99_999_999_999_999_999 => Precision::Micros,
+      _ => Precision::Nanos,
+    }
+  }
+
   pub fn try_downcast(&self) -> Option<Self> {
     let p = match self {
       Precision::Weeks => Precision::Days,
@@ -47,17 +132,59 @@ impl Precision {
       Precision::Hours => Precision::Mins,
       Precision::Mins => Precision::Secs,
       Precision::Secs => Precision::Millis,
-      Precision::Millis => Precision::Nanos,
+      Precision::Millis => Precision::Micros,
+      Precision::Micros => Precision::Nanos,
       Precision::Nanos => return None,
     };
     Some(p)
   }
 
+  /// The short unit suffix used when writing human durations
+  pub fn unit(&self) -> &'static str {
+    match self {
+      Precision::Weeks => "w",
+      Precision::Days => "d",
+      Precision::Hours => "h",
+      Precision::Mins => "m",
+      Precision::Secs => "s",
+      Precision::Millis => "ms",
+      Precision::Micros => "us",
+      Precision::Nanos => "ns",
+    }
+  }
+
   pub fn parse(&self, ts: i64) -> LocalResult<DateTime<Utc>> {
     match self {
       Precision::Millis => Utc.timestamp_millis_opt(ts),
+      Precision::Micros => Utc.timestamp_opt(
+        ts.div_euclid(1_000_000),
+        ts.rem_euclid(1_000_000) as u32 * 1000,
+      ),
       Precision::Nanos => LocalResult::Single(Utc.timestamp_nanos(ts)),
-      _ => Utc.timestamp_opt(ts * self.seconds_per(), 0),
+      _ => match ts.checked_mul(self.seconds_per()) {
+        Some(secs) => Utc.timestamp_opt(secs, 0),
+        None => LocalResult::None,
+      },
+    }
+  }
+
+  /// Parses a stamp with a fractional part, given as billionths of this precision
+  pub fn parse_fractional(&self, whole: i64, billionths: u32) -> LocalResult<DateTime<Utc>> {
+    let per = self.nanos_per() as i128;
+    let nanos = whole as i128 * per + billionths as i128 * per / 1_000_000_000;
+    let secs = nanos.div_euclid(1_000_000_000);
+    match i64::try_from(secs) {
+      Ok(secs) => Utc.timestamp_opt(secs, nanos.rem_euclid(1_000_000_000) as u32),
+      Err(_) => LocalResult::None,
+    }
+  }
+
+  pub fn nanos_per(&self) -> i64 {
+    match self {
+      Precision::Millis => 1_000_000,
+      Precision::Micros => 1_000,
+      Precision::Nanos => 1,
+      _ => self.seconds_per() * 1_000_000_000,
     }
   }
 
@@ -73,16 +200,43 @@ impl Precision {
     }
   }
 
-  pub fn as_stamp<T>(&self, dt: &DateTime<T>) -> i64
+  /// The stamp counting the date in this unit. Nanos only reach the years
+  /// 1677 to 2262 before overflowing, so fail outside them
+  pub fn as_stamp<T>(&self, dt: &DateTime<T>) -> Result<i64, String>
   where
     T: TimeZone,
   {
     match self {
-      Precision::Secs => dt.timestamp(),
-      Precision::Millis => dt.timestamp_millis(),
-      Precision::Nanos => dt.timestamp_nanos(),
-      _ => dt.timestamp() / self.seconds_per(),
+      Precision::Secs => Ok(dt.timestamp()),
+      Precision::Millis => Ok(dt.timestamp_millis()),
+      Precision::Micros => Ok(dt.timestamp_micros()),
+      Precision::Nanos => dt
+        .timestamp()
+        .checked_mul(1_000_000_000)
+        .and_then(|n| n.checked_add(dt.timestamp_subsec_nanos() as i64))
+        .ok_or_else(|| format!("Could not print {} as nanos: out of range", dt.naive_utc())),
+      // Floor so times before 1970 land in the unit they fall within
+      _ => Ok(dt.timestamp().div_euclid(self.seconds_per())),
+    }
+  }
+
+  /// Like `as_stamp`, but choosing how any finer digits are dropped
+  pub fn as_rounded_stamp<T>(&self, dt: &DateTime<T>, rounding: Rounding) -> Result<i64, String>
+  where
+    T: TimeZone,
+  {
+    let stamp = self.as_stamp(dt)?;
+    if rounding == Rounding::Truncate {
+      return Ok(stamp);
     }
+    let nanos = dt.timestamp() as i128 * 1_000_000_000 + dt.timestamp_subsec_nanos() as i128;
+    let per = self.nanos_per() as i128;
+    let excess = nanos - stamp as i128 * per;
+    Ok(match (excess * 2).cmp(&per) {
+      std::cmp::Ordering::Greater => stamp + 1,
+      std::cmp::Ordering::Equal if stamp % 2 != 0 => stamp + 1,
+      _ => stamp,
+    })
   }
 }
 
@@ -90,10 +244,11 @@ impl Precision {
 mod test {
   use rstest::*;
 
-  use super::Precision;
+  use super::{Precision, Rounding};
 
   #[rstest]
   #[case(Precision::Millis, 0)]
+  #[case(Precision::Micros, 0)]
   #[case(Precision::Nanos, 0)]
   #[case(Precision::Secs, 1)]
   #[case(Precision::Mins, 60)]
@@ -103,4 +258,95 @@ mod test {
   fn seconds_per(#[case] pre: Precision, #[case] exp: i64) {
     assert_eq!(pre.seconds_per(), exp)
   }
+
+  #[rstest]
+  #[case(1679258022, Precision::Secs)]
+  #[case(-1679258022, Precision::Secs)]
+  #[case(0, Precision::Secs)]
+  #[case(1679258022123, Precision::Millis)]
+  #[case(1679258022123456, Precision::Micros)]
+  #[case(1679258022123456789, Precision::Nanos)]
+  fn infer(#[case] stamp: i64, #[case] exp: Precision) {
+    assert_eq!(Precision::infer(stamp), exp)
+  }
+
+  #[rstest]
+  #[case(Precision::Secs, 1679258022)]
+  #[case(Precision::Millis, 1679258022123)]
+  #[case(Precision::Micros, 1679258022123456)]
+  #[case(Precision::Micros, -1)]
+  #[case(Precision::Nanos, 1679258022123456789)]
+  #[case(Precision::Secs, -1679258022)]
+  #[case(Precision::Millis, -1679258022123)]
+  #[case(Precision::Nanos, -1679258022123456789)]
+  #[case(Precision::Hours, -1)]
+  #[case(Precision::Weeks, -2)]
+  fn round_trip(#[case] pre: Precision, #[case] stamp: i64) {
+    let parsed = pre.parse(stamp).single().unwrap();
+    assert_eq!(pre.as_stamp(&parsed), Ok(stamp))
+  }
+
+  #[rstest]
+  #[case(Precision::Millis, 1_499_999, Rounding::Truncate, 1)]
+  #[case(Precision::Millis, 1_499_999, Rounding::HalfEven, 1)]
+  #[case(Precision::Millis, 1_500_000, Rounding::HalfEven, 2)]
+  #[case(Precision::Millis, 2_500_000, Rounding::HalfEven, 2)]
+  #[case(Precision::Millis, 2_500_001, Rounding::HalfEven, 3)]
+  #[case(Precision::Millis, -1_500_000, Rounding::Truncate, -2)]
+  #[case(Precision::Millis, -1_500_000, Rounding::HalfEven, -2)]
+  #[case(Precision::Millis, -2_500_000, Rounding::HalfEven, -2)]
+  #[case(Precision::Secs, 1_500_000_000, Rounding::HalfEven, 2)]
+  #[case(Precision::Nanos, 1_500_000_000, Rounding::HalfEven, 1_500_000_000)]
+  fn rounded_stamp(
+    #[case] pre: Precision,
+    #[case] nanos: i64,
+    #[case] rounding: Rounding,
+    #[case] exp: i64,
+  ) {
+    let dt = Precision::Nanos.parse(nanos).unwrap();
+    assert_eq!(pre.as_rounded_stamp(&dt, rounding), Ok(exp))
+  }
+
+  #[rstest]
+  #[case(Precision::Hours, -1, -1)]
+  #[case(Precision::Hours, -3600, -1)]
+  #[case(Precision::Hours, -3601, -2)]
+  #[case(Precision::Days, 86399, 0)]
+  fn coarse_stamp(#[case] pre: Precision, #[case] secs: i64, #[case] exp: i64) {
+    let dt = Precision::Secs.parse(secs).unwrap();
+    assert_eq!(pre.as_stamp(&dt), Ok(exp))
+  }
+
+  #[test]
+  fn coarse_overflow() {
+    assert!(Precision::Weeks.parse(i64::MIN).single().is_none())
+  }
+
+  #[rstest]
+  #[case(Precision::Nanos, Err("Could not print 1600-01-01 00:00:00 as nanos: out of range".into()))]
+  #[case(Precision::Micros, Ok(-11676096000000000))]
+  fn stamp_range(#[case] pre: Precision, #[case] exp: Result<i64, String>) {
+    let dt = Precision::Secs.parse(-11676096000).unwrap();
+    assert_eq!(pre.as_stamp(&dt), exp)
+  }
+
+  #[rstest]
+  #[case(Precision::Secs, 1679258022, 123456000, 1679258022123456000)]
+  #[case(Precision::Millis, 1679258022123, 456789000, 1679258022123456789)]
+  #[case(Precision::Micros, 1679258022123456, 789000000, 1679258022123456789)]
+  #[case(Precision::Nanos, 1679258022123456789, 500000000, 1679258022123456789)]
+  #[case(Precision::Secs, -2, 500000000, -1500000000)]
+  #[case(Precision::Mins, 1, 500000000, 90000000000)]
+  fn parse_fractional(
+    #[case] pre: Precision,
+    #[case] whole: i64,
+    #[case] billionths: u32,
+    #[case] exp_nanos: i64,
+  ) {
+    let parsed = pre.parse_fractional(whole, billionths).single();
+    assert_eq!(
+      parsed.map(|dt| Precision::Nanos.as_stamp(&dt)),
+      Some(Ok(exp_nanos))
+    )
+  }
 }
@@ -0,0 +1,167 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Days, Duration, FixedOffset, Offset, TimeZone, Weekday};
+
+use super::Zone;
+use crate::hduration::HDuration;
+
+/// Natural language inputs that only make sense relative to the current instant
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelativeInput {
+  /// Whole days away from now, keeping the time of day ("yesterday")
+  Days(i64),
+  /// Midnight of the closest weekday before or after today ("last friday")
+  Weekday { day: Weekday, forward: bool },
+  /// A duration before or after now ("2h ago", "in 3 days")
+  Offset { dur: HDuration, forward: bool },
+}
+
+impl RelativeInput {
+  pub fn resolve(&self, now: &DateTime<Zone>) -> Result<DateTime<FixedOffset>, String> {
+    let out_of_range = || format!("{:?} is out of range", self);
+    let dt = match self {
+      RelativeInput::Days(d) if *d < 0 => now.checked_sub_days(Days::new(d.unsigned_abs())),
+      RelativeInput::Days(d) => now.checked_add_days(Days::new(*d as u64)),
+      RelativeInput::Weekday { day, forward } => {
+        let mut date = now.date_naive();
+        loop {
+          date = if *forward {
+            date.succ_opt()
+          } else {
+            date.pred_opt()
+          }
+          .ok_or_else(out_of_range)?;
+          if date.weekday() == *day {
+            break;
+          }
+        }
+        let midnight = date.and_hms_opt(0, 0, 0).ok_or_else(out_of_range)?;
+        now.timezone().from_local_datetime(&midnight).earliest()
+      }
+      RelativeInput::Offset { dur, forward } => {
+        let d = Duration::from_std(dur.inner).map_err(|e| format!("{}", e))?;
+        // A negative duration runs the other way, so "in -2h" is 2h ago
+        if *forward != dur.negative {
+          now.checked_add_signed(d)
+        } else {
+          now.checked_sub_signed(d)
+        }
+      }
+    };
+    dt.map(|dt| dt.with_timezone(&dt.offset().fix()))
+      .ok_or_else(out_of_range)
+  }
+}
+
+impl FromStr for RelativeInput {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let lower = s.trim().to_lowercase();
+    let rel = match lower.as_str() {
+      "today" => RelativeInput::Days(0),
+      "yesterday" => RelativeInput::Days(-1),
+      "tomorrow" => RelativeInput::Days(1),
+      _ => {
+        if let Some(day) = lower.strip_prefix("last ") {
+          RelativeInput::Weekday {
+            day: parse_weekday(day)?,
+            forward: false,
+          }
+        } else if let Some(day) = lower.strip_prefix("next ") {
+          RelativeInput::Weekday {
+            day: parse_weekday(day)?,
+            forward: true,
+          }
+        } else if let Some(dur) = lower.strip_suffix(" ago") {
+          RelativeInput::Offset {
+            dur: parse_duration(dur)?,
+            forward: false,
+          }
+        } else if let Some(dur) = lower.strip_prefix("in ") {
+          RelativeInput::Offset {
+            dur: parse_duration(dur)?,
+            forward: true,
+          }
+        } else {
+          return Err(format!("Unknown relative time: {}", s));
+        }
+      }
+    };
+    Ok(rel)
+  }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+  s.trim()
+    .parse::<Weekday>()
+    .map_err(|_| format!("Unknown weekday: {}", s))
+}
+
+/// Accepts either a human duration ("3h 20m") or a spelled out one ("3 hours")
+fn parse_duration(s: &str) -> Result<HDuration, String> {
+  let s = s.trim();
+  if let Ok(dur) = s.parse::<HDuration>() {
+    return Ok(dur);
+  }
+  let (count, unit) = s
+    .split_once(' ')
+    .ok_or_else(|| format!("Unknown duration: {}", s))?;
+  let unit = match unit.trim().trim_end_matches('s') {
+    "week" => "w",
+    "day" => "d",
+    "hour" => "h",
+    "minute" | "min" => "m",
+    "second" | "sec" => "s",
+    "millisecond" | "milli" => "ms",
+    "nanosecond" | "nano" => "ns",
+    _ => return Err(format!("Unknown duration unit: {}", unit)),
+  };
+  format!("{}{}", count.trim(), unit).parse()
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{TimeZone, Weekday};
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::{RelativeInput, Zone};
+  use crate::hduration::HDuration;
+
+  #[rstest]
+  #[case("yesterday", RelativeInput::Days(-1))]
+  #[case("Tomorrow", RelativeInput::Days(1))]
+  #[case("last friday", RelativeInput::Weekday { day: Weekday::Fri, forward: false })]
+  #[case("next mon", RelativeInput::Weekday { day: Weekday::Mon, forward: true })]
+  #[case("2h ago", RelativeInput::Offset { dur: HDuration::new(7200, 0, false), forward: false })]
+  #[case("3 hours ago", RelativeInput::Offset { dur: HDuration::new(10800, 0, false), forward: false })]
+  #[case("in 1 day", RelativeInput::Offset { dur: HDuration::new(86400, 0, false), forward: true })]
+  fn from_str(#[case] input: &str, #[case] expected: RelativeInput) {
+    assert_eq!(input.parse::<RelativeInput>(), Ok(expected))
+  }
+
+  #[rstest]
+  #[case("someday")]
+  #[case("last blursday")]
+  #[case("3 fortnights ago")]
+  fn invalid_from_str(#[case] input: &str) {
+    assert!(input.parse::<RelativeInput>().is_err())
+  }
+
+  #[rstest]
+  // Sunday 2023-03-19T16:33:42-04:00
+  #[case("yesterday", "2023-03-18T16:33:42-04:00")]
+  #[case("last friday", "2023-03-17T00:00:00-04:00")]
+  #[case("next sunday", "2023-03-26T00:00:00-04:00")]
+  #[case("90m ago", "2023-03-19T15:03:42-04:00")]
+  #[case("in -2h", "2023-03-19T14:33:42-04:00")]
+  #[case("-2h ago", "2023-03-19T18:33:42-04:00")]
+  fn resolve(#[case] input: &str, #[case] expected: &str) {
+    let now = Zone::from(Tz::America__New_York)
+      .timestamp_opt(1679258022, 0)
+      .unwrap();
+    let resolved = input.parse::<RelativeInput>().unwrap().resolve(&now);
+    assert_eq!(resolved.map(|dt| dt.to_rfc3339()), Ok(expected.into()))
+  }
+}
@@ -0,0 +1,389 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike, Weekday};
+use chrono_tz::Tz;
+
+/// The values one component of a schedule may take, within its domain
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+  min: u32,
+  max: u32,
+  allowed: Vec<u32>,
+}
+
+impl Field {
+  fn any(min: u32, max: u32) -> Self {
+    Field {
+      min,
+      max,
+      allowed: (min..=max).collect(),
+    }
+  }
+
+  fn contains(&self, v: u32) -> bool {
+    self.allowed.binary_search(&v).is_ok()
+  }
+
+  /// Parses a comma separated list of values, `a..b` ranges, and `/step`
+  /// repetitions, with `*` covering the whole domain
+  fn parse(
+    s: &str,
+    min: u32,
+    max: u32,
+    range_sep: &str,
+    value: impl Fn(&str) -> Option<u32>,
+  ) -> Result<Self, String> {
+    let invalid = || format!("Invalid value {} for range {}-{}", s, min, max);
+    let mut allowed = Vec::new();
+    for item in s.split(',') {
+      let (span, step) = match item.split_once('/') {
+        Some((span, step)) => match step.parse::<u32>() {
+          Ok(step) if step > 0 => (span, Some(step)),
+          _ => return Err(invalid()),
+        },
+        None => (item, None),
+      };
+      let (lo, hi) = if span == "*" {
+        (min, max)
+      } else if let Some((lo, hi)) = span.split_once(range_sep) {
+        (
+          value(lo).ok_or_else(invalid)?,
+          value(hi).ok_or_else(invalid)?,
+        )
+      } else {
+        let v = value(span).ok_or_else(invalid)?;
+        (v, if step.is_some() { max } else { v })
+      };
+      if lo < min || hi > max || lo > hi {
+        return Err(invalid());
+      }
+      allowed.extend((lo..=hi).step_by(step.unwrap_or(1) as usize));
+    }
+    allowed.sort_unstable();
+    allowed.dedup();
+    Ok(Field { min, max, allowed })
+  }
+}
+
+fn weekday(s: &str) -> Option<u32> {
+  s.parse::<Weekday>().ok().map(|d| d.num_days_from_monday())
+}
+
+/// Reads a cron month, given as a number or a name like JAN
+fn cron_month(s: &str) -> Option<u32> {
+  const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+  ];
+  let lower = s.to_lowercase();
+  s.parse::<u32>().ok().or_else(|| {
+    MONTHS
+      .iter()
+      .position(|m| *m == lower)
+      .map(|i| i as u32 + 1)
+  })
+}
+
+/// Reads a cron weekday, where both 0 and 7 are Sunday
+fn cron_weekday(s: &str) -> Option<u32> {
+  s.parse::<u32>()
+    .ok()
+    .or_else(|| weekday(s).map(|d| (d + 1) % 7))
+}
+
+/// A recurring set of wall clock times, as described by a calendar expression
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+  weekdays: Field,
+  years: Field,
+  months: Field,
+  days: Field,
+  hours: Field,
+  minutes: Field,
+  seconds: Field,
+  /// Whether matching either the day of month or weekday suffices, as cron
+  /// does when both are restricted
+  either_day: bool,
+  /// The zone the expression names, if any
+  pub zone: Option<Tz>,
+}
+
+impl Schedule {
+  /// Parses a systemd OnCalendar expression, such as "Mon..Fri *-*-* 10:00"
+  /// or "weekly", with an optional trailing timezone
+  pub fn oncalendar(s: &str) -> Result<Self, String> {
+    let mut tokens = s.split_whitespace().collect::<Vec<_>>();
+    let zone = match tokens.last().map(|t| t.parse::<Tz>()) {
+      Some(Ok(tz)) if tokens.len() > 1 => {
+        tokens.pop();
+        Some(tz)
+      }
+      _ => None,
+    };
+    let expanded = match tokens.as_slice() {
+      [shorthand] => match shorthand.to_lowercase().as_str() {
+        "minutely" => Some("*-*-* *:*:00"),
+        "hourly" => Some("*-*-* *:00:00"),
+        "daily" => Some("*-*-* 00:00:00"),
+        "weekly" => Some("Mon *-*-* 00:00:00"),
+        "monthly" => Some("*-*-01 00:00:00"),
+        "quarterly" => Some("*-01,04,07,10-01 00:00:00"),
+        "semiannually" => Some("*-01,07-01 00:00:00"),
+        "yearly" | "annually" => Some("*-01-01 00:00:00"),
+        _ => None,
+      },
+      _ => None,
+    };
+    if let Some(expanded) = expanded {
+      tokens = expanded.split(' ').collect();
+    }
+
+    let mut weekdays = Field::any(0, 6);
+    if let Some(first) = tokens.first() {
+      if first.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        weekdays = Field::parse(first, 0, 6, "..", weekday)?;
+        tokens.remove(0);
+      }
+    }
+    let (date, time) = match tokens.as_slice() {
+      [] => ("*-*-*", "00:00:00"),
+      [one] if one.contains(':') => ("*-*-*", *one),
+      [one] => (*one, "00:00:00"),
+      [date, time] => (*date, *time),
+      _ => return Err(format!("Unexpected calendar expression: {}", s)),
+    };
+
+    let num = |v: &str| v.parse::<u32>().ok();
+    let (years, months, days) = match date.split('-').collect::<Vec<_>>().as_slice() {
+      [y, m, d] => (*y, *m, *d),
+      [m, d] => ("*", *m, *d),
+      _ => return Err(format!("Invalid date {}, expected Y-M-D", date)),
+    };
+    let (hours, minutes, seconds) = match time.split(':').collect::<Vec<_>>().as_slice() {
+      [h, m] => (*h, *m, "00"),
+      [h, m, s] => (*h, *m, *s),
+      _ => return Err(format!("Invalid time {}, expected H:M:S", time)),
+    };
+    Ok(Schedule {
+      weekdays,
+      years: Field::parse(years, 1970, 2199, "..", num)?,
+      months: Field::parse(months, 1, 12, "..", num)?,
+      days: Field::parse(days, 1, 31, "..", num)?,
+      hours: Field::parse(hours, 0, 23, "..", num)?,
+      minutes: Field::parse(minutes, 0, 59, "..", num)?,
+      seconds: Field::parse(seconds, 0, 59, "..", num)?,
+      either_day: false,
+      zone,
+    })
+  }
+
+  /// Parses a five field cron expression, such as "0 2 * * SUN", or one of
+  /// the @daily style shorthands
+  pub fn cron(s: &str) -> Result<Self, String> {
+    let expanded = match s.trim().to_lowercase().as_str() {
+      "@yearly" | "@annually" => "0 0 1 1 *",
+      "@monthly" => "0 0 1 * *",
+      "@weekly" => "0 0 * * 0",
+      "@daily" | "@midnight" => "0 0 * * *",
+      "@hourly" => "0 * * * *",
+      _ => s,
+    };
+    let num = |v: &str| v.parse::<u32>().ok();
+    let [minutes, hours, days, months, weekdays] =
+      expanded.split_whitespace().collect::<Vec<_>>()[..]
+    else {
+      return Err(format!("Expected 5 cron fields but got: {}", s));
+    };
+    let sundays = Field::parse(weekdays, 0, 7, "-", cron_weekday)?;
+    let mut mondays = sundays
+      .allowed
+      .iter()
+      .map(|d| (d + 6) % 7)
+      .collect::<Vec<_>>();
+    mondays.sort_unstable();
+    mondays.dedup();
+    Ok(Schedule {
+      weekdays: Field {
+        min: 0,
+        max: 6,
+        allowed: mondays,
+      },
+      years: Field::any(1970, 2199),
+      months: Field::parse(months, 1, 12, "-", cron_month)?,
+      days: Field::parse(days, 1, 31, "-", num)?,
+      hours: Field::parse(hours, 0, 23, "-", num)?,
+      minutes: Field::parse(minutes, 0, 59, "-", num)?,
+      seconds: Field::any(0, 0),
+      either_day: !days.starts_with('*') && !weekdays.starts_with('*'),
+      zone: None,
+    })
+  }
+
+  fn matches_date(&self, date: NaiveDate) -> bool {
+    u32::try_from(date.year()).is_ok_and(|y| self.years.contains(y))
+      && self.months.contains(date.month())
+      && self.matches_day(date)
+  }
+
+  fn matches_day(&self, date: NaiveDate) -> bool {
+    let day = self.days.contains(date.day());
+    let weekday = self
+      .weekdays
+      .contains(date.weekday().num_days_from_monday());
+    if self.either_day {
+      day || weekday
+    } else {
+      day && weekday
+    }
+  }
+
+  /// Whether the wall clock time is one the schedule describes
+  pub fn matches(&self, naive: &NaiveDateTime) -> bool {
+    self.matches_date(naive.date())
+      && self.hours.contains(naive.hour())
+      && self.minutes.contains(naive.minute())
+      && self.seconds.contains(naive.second())
+  }
+
+  /// Wall clock times the schedule describes on the given date that do not
+  /// exist in the zone, having been skipped by DST
+  pub fn skipped_on(&self, date: NaiveDate, tz: &impl TimeZone) -> Vec<NaiveDateTime> {
+    if !self.matches_date(date) {
+      return Vec::new();
+    }
+    let mut skipped = Vec::new();
+    for h in &self.hours.allowed {
+      for m in &self.minutes.allowed {
+        for s in &self.seconds.allowed {
+          if let Some(naive) = date.and_hms_opt(*h, *m, *s) {
+            if tz.from_local_datetime(&naive).earliest().is_none() {
+              skipped.push(naive);
+            }
+          }
+        }
+      }
+    }
+    skipped
+  }
+
+  /// The first time the schedule fires strictly after the given one, in the
+  /// given time's zone. Wall clock times skipped by DST never fire, and
+  /// repeated ones fire on their first occurrence
+  pub fn next_after<T: TimeZone>(&self, after: &DateTime<T>) -> Option<DateTime<T>> {
+    let tz = after.timezone();
+    let mut date = after.date_naive();
+    while date.year() <= self.years.max as i32 {
+      if self.matches_date(date) {
+        for h in &self.hours.allowed {
+          for m in &self.minutes.allowed {
+            for s in &self.seconds.allowed {
+              let fire = date
+                .and_hms_opt(*h, *m, *s)
+                .and_then(|naive| tz.from_local_datetime(&naive).earliest())
+                .filter(|fire| fire > after);
+              if fire.is_some() {
+                return fire;
+              }
+            }
+          }
+        }
+      }
+      date = date.succ_opt()?;
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{DateTime, TimeZone};
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::Schedule;
+
+  fn fires(expr: &str, from: &str, count: usize) -> Vec<String> {
+    let schedule = Schedule::oncalendar(expr).unwrap();
+    let tz = schedule.zone.unwrap_or(Tz::UTC);
+    let mut at = DateTime::parse_from_rfc3339(from)
+      .unwrap()
+      .with_timezone(&tz);
+    let mut out = Vec::new();
+    for _ in 0..count {
+      at = schedule.next_after(&at).unwrap();
+      out.push(at.to_rfc3339());
+    }
+    out
+  }
+
+  #[rstest]
+  // Friday evening, so the weekend is skipped
+  #[case("Mon..Fri *-*-* 10:00", "2023-03-17T18:00:00Z", vec!["2023-03-20T10:00:00+00:00", "2023-03-21T10:00:00+00:00"])]
+  #[case("weekly", "2023-03-17T18:00:00Z", vec!["2023-03-20T00:00:00+00:00", "2023-03-27T00:00:00+00:00"])]
+  #[case("*-*-01,15 12:00", "2023-03-17T18:00:00Z", vec!["2023-04-01T12:00:00+00:00", "2023-04-15T12:00:00+00:00"])]
+  #[case("*:0/20", "2023-03-17T18:05:00Z", vec!["2023-03-17T18:20:00+00:00", "2023-03-17T18:40:00+00:00"])]
+  #[case("Sat,Sun 2023-*-* 09:30:15", "2023-03-17T18:00:00Z", vec!["2023-03-18T09:30:15+00:00", "2023-03-19T09:30:15+00:00"])]
+  #[case("*-02-29", "2023-03-17T18:00:00Z", vec!["2024-02-29T00:00:00+00:00", "2028-02-29T00:00:00+00:00"])]
+  // 02:30 is skipped by the spring forward
+  #[case("*-*-* 02:30 America/New_York", "2023-03-11T12:00:00Z", vec!["2023-03-13T02:30:00-04:00", "2023-03-14T02:30:00-04:00"])]
+  fn next(#[case] expr: &str, #[case] from: &str, #[case] expected: Vec<&str>) {
+    assert_eq!(fires(expr, from, expected.len()), expected)
+  }
+
+  #[rstest]
+  #[case("0 2 * * SUN", "2023-03-17T18:00:00Z", vec!["2023-03-19T02:00:00+00:00", "2023-03-26T02:00:00+00:00"])]
+  #[case("*/30 9-10 * * *", "2023-03-17T09:10:00Z", vec!["2023-03-17T09:30:00+00:00", "2023-03-17T10:00:00+00:00", "2023-03-17T10:30:00+00:00", "2023-03-18T09:00:00+00:00"])]
+  // Both day fields restricted, so either may match
+  #[case("0 0 1 * MON", "2023-03-17T18:00:00Z", vec!["2023-03-20T00:00:00+00:00", "2023-03-27T00:00:00+00:00", "2023-04-01T00:00:00+00:00"])]
+  #[case("0 0 1 jan 7", "2023-03-17T18:00:00Z", vec!["2024-01-01T00:00:00+00:00", "2024-01-07T00:00:00+00:00"])]
+  #[case("@monthly", "2023-03-17T18:00:00Z", vec!["2023-04-01T00:00:00+00:00"])]
+  fn next_cron(#[case] expr: &str, #[case] from: &str, #[case] expected: Vec<&str>) {
+    let schedule = Schedule::cron(expr).unwrap();
+    let mut at = DateTime::parse_from_rfc3339(from)
+      .unwrap()
+      .with_timezone(&Tz::UTC);
+    let mut out = Vec::new();
+    for _ in 0..expected.len() {
+      at = schedule.next_after(&at).unwrap();
+      out.push(at.to_rfc3339());
+    }
+    assert_eq!(out, expected)
+  }
+
+  #[test]
+  fn skipped() {
+    let schedule = Schedule::cron("0,30 2 * * SUN").unwrap();
+    let date = "2025-03-30".parse().unwrap();
+    let skipped = schedule.skipped_on(date, &Tz::Europe__Berlin);
+    assert_eq!(
+      skipped.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+      vec!["2025-03-30 02:00:00", "2025-03-30 02:30:00"]
+    );
+    assert!(schedule.matches(&skipped[0]));
+    assert!(schedule.skipped_on(date, &Tz::UTC).is_empty());
+  }
+
+  #[rstest]
+  #[case("0 2 * *")]
+  #[case("60 * * * *")]
+  #[case("*/0 * * * *")]
+  #[case("0/x * * * *")]
+  #[case("0 0 * FOO *")]
+  fn invalid_cron(#[case] expr: &str) {
+    assert!(Schedule::cron(expr).is_err())
+  }
+
+  #[test]
+  fn exhausted() {
+    let schedule = Schedule::oncalendar("2020-01-01").unwrap();
+    let now = Tz::UTC.timestamp_opt(1679258022, 0).unwrap();
+    assert_eq!(schedule.next_after(&now), None)
+  }
+
+  #[rstest]
+  #[case("Blursday *-*-* 10:00")]
+  #[case("*-13-01")]
+  #[case("*-*-* 25:00")]
+  #[case("*-*-* 10:00 extra tokens")]
+  #[case("5..3 10:00")]
+  fn invalid(#[case] expr: &str) {
+    assert!(Schedule::oncalendar(expr).is_err())
+  }
+}
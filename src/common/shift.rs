@@ -0,0 +1,65 @@
+use chrono::{DateTime, Duration};
+use clap::Args;
+
+use super::Zone;
+use crate::hduration::HDuration;
+
+/// Seeded shifts land within this many seconds either side of the real time
+const SEEDED_RANGE: i64 = 365 * 86400;
+
+#[derive(Args)]
+pub struct ShiftArgs {
+  /// Offset every time by the same hidden amount, keeping their spacing. Takes
+  /// a duration, or any other text as a seed picking a shift within a year
+  #[arg(long, allow_hyphen_values = true, value_parser = parse_secret)]
+  shift_secret: Option<Duration>,
+}
+
+/// 64 bit FNV-1a, which is stable across builds unlike the std hashers
+fn fnv1a(s: &str) -> u64 {
+  s.bytes().fold(0xcbf29ce484222325, |h, b| {
+    (h ^ b as u64).wrapping_mul(0x100000001b3)
+  })
+}
+
+fn parse_secret(s: &str) -> Result<Duration, String> {
+  if let Ok(dur) = s.parse::<HDuration>() {
+    return dur.to_chrono();
+  }
+  let span = 2 * SEEDED_RANGE as u64 + 1;
+  Ok(Duration::seconds((fnv1a(s) % span) as i64 - SEEDED_RANGE))
+}
+
+impl ShiftArgs {
+  pub fn apply(&self, dt: DateTime<Zone>) -> Result<DateTime<Zone>, String> {
+    match self.shift_secret {
+      None => Ok(dt),
+      Some(shift) => dt
+        .checked_add_signed(shift)
+        .ok_or_else(|| "Shifting by the secret is out of range".to_string()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::Duration;
+  use rstest::*;
+
+  use super::{parse_secret, SEEDED_RANGE};
+
+  #[rstest]
+  #[case("2h", Duration::hours(2))]
+  #[case("-90m", Duration::minutes(-90))]
+  fn duration(#[case] input: &str, #[case] expected: Duration) {
+    assert_eq!(parse_secret(input), Ok(expected))
+  }
+
+  #[test]
+  fn seeded() {
+    let shift = parse_secret("hunter2").unwrap();
+    assert_eq!(parse_secret("hunter2"), Ok(shift));
+    assert_ne!(parse_secret("hunter3"), Ok(shift));
+    assert!(shift.num_seconds().abs() <= SEEDED_RANGE);
+  }
+}
@@ -0,0 +1,179 @@
+use chrono::{DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveTime, Weekday};
+use clap::{Args, ValueEnum};
+
+use super::{dst::relocal, WeekStart, Zone};
+
+/// A calendar period times can be moved to the bounds of
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Period {
+  Day,
+  Week,
+  Month,
+  Quarter,
+  Year,
+}
+
+impl Period {
+  /// The first day of the period holding the date
+  fn start(&self, date: NaiveDate, week_start: WeekStart) -> Option<NaiveDate> {
+    match self {
+      Period::Day => Some(date),
+      Period::Week => date.checked_sub_days(Days::new(week_start.days_into(date.weekday()) as u64)),
+      Period::Month => date.with_day(1),
+      Period::Quarter => date.with_day(1)?.with_month(date.month0() / 3 * 3 + 1),
+      Period::Year => date.with_day(1)?.with_month(1),
+    }
+  }
+
+  /// The first day of the period after the one holding the date
+  fn next(&self, date: NaiveDate, week_start: WeekStart) -> Option<NaiveDate> {
+    let start = self.start(date, week_start)?;
+    match self {
+      Period::Day => start.checked_add_days(Days::new(1)),
+      Period::Week => start.checked_add_days(Days::new(7)),
+      Period::Month => start.checked_add_months(Months::new(1)),
+      Period::Quarter => start.checked_add_months(Months::new(3)),
+      Period::Year => start.checked_add_months(Months::new(12)),
+    }
+  }
+}
+
+#[derive(Args)]
+pub struct SnapArgs {
+  /// Move all times to the following occurrence of this weekday, a week on
+  /// when they already fall on it
+  #[arg(long, group = "snap")]
+  next: Option<Weekday>,
+
+  /// Move all times to the preceding occurrence of this weekday, a week back
+  /// when they already fall on it
+  #[arg(long, group = "snap", conflicts_with = "next")]
+  prev: Option<Weekday>,
+
+  /// Move to midnight on the new day rather than keeping the time of day
+  #[arg(long, requires = "snap")]
+  midnight: bool,
+
+  /// Move all times to the first instant of their period in the target
+  /// timezone
+  #[arg(value_enum, long)]
+  start_of: Option<Period>,
+
+  /// Move all times to the last instant of their period in the target
+  /// timezone, a nanosecond before the next one starts
+  #[arg(value_enum, long, conflicts_with = "start_of")]
+  end_of: Option<Period>,
+}
+
+impl SnapArgs {
+  pub fn apply(&self, dt: DateTime<Zone>, week_start: WeekStart) -> Result<DateTime<Zone>, String> {
+    let dt = self.weekday(dt)?;
+    let local = dt.naive_local().date();
+    let bound = match (self.start_of, self.end_of) {
+      (Some(period), _) => period.start(local, week_start),
+      (None, Some(period)) => period.next(local, week_start),
+      (None, None) => return Ok(dt),
+    }
+    .ok_or("Moving to the period bound is out of range")?;
+    let bound = relocal(&dt, &bound.and_time(NaiveTime::MIN))?;
+    match self.end_of {
+      Some(_) => Ok(bound - Duration::nanoseconds(1)),
+      None => Ok(bound),
+    }
+  }
+
+  fn weekday(&self, dt: DateTime<Zone>) -> Result<DateTime<Zone>, String> {
+    let local = dt.naive_local();
+    let from = local.weekday().num_days_from_monday();
+    let date = match (self.next, self.prev) {
+      (Some(day), _) => {
+        let ahead = (day.num_days_from_monday() + 6 - from) % 7 + 1;
+        local.date().checked_add_days(Days::new(ahead as u64))
+      }
+      (None, Some(day)) => {
+        let back = (from + 6 - day.num_days_from_monday()) % 7 + 1;
+        local.date().checked_sub_days(Days::new(back as u64))
+      }
+      (None, None) => return Ok(dt),
+    }
+    .ok_or("Moving to the weekday is out of range")?;
+    let time = match self.midnight {
+      true => NaiveTime::MIN,
+      false => local.time(),
+    };
+    relocal(&dt, &date.and_time(time))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{TimeZone, Weekday};
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::{Period, SnapArgs};
+  use crate::common::{WeekStart, Zone};
+
+  #[rstest]
+  // 2023-03-17 is a Friday
+  #[case(Some(Weekday::Fri), None, false, "2023-03-24T15:30:00+00:00")]
+  #[case(Some(Weekday::Mon), None, false, "2023-03-20T15:30:00+00:00")]
+  #[case(Some(Weekday::Sat), None, true, "2023-03-18T00:00:00+00:00")]
+  #[case(None, Some(Weekday::Fri), false, "2023-03-10T15:30:00+00:00")]
+  #[case(None, Some(Weekday::Thu), true, "2023-03-16T00:00:00+00:00")]
+  #[case(None, None, false, "2023-03-17T15:30:00+00:00")]
+  fn apply(
+    #[case] next: Option<Weekday>,
+    #[case] prev: Option<Weekday>,
+    #[case] midnight: bool,
+    #[case] expected: &str,
+  ) {
+    let args = SnapArgs {
+      next,
+      prev,
+      midnight,
+      start_of: None,
+      end_of: None,
+    };
+    let dt = Zone::from(Tz::UTC)
+      .with_ymd_and_hms(2023, 3, 17, 15, 30, 0)
+      .unwrap();
+    assert_eq!(
+      args.apply(dt, WeekStart::Mon).map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
+
+  #[rstest]
+  // 2023-08-17 is a Thursday, and London is an hour ahead of UTC in summer
+  #[case(Some(Period::Day), None, "2023-08-17T00:00:00+01:00")]
+  #[case(Some(Period::Week), None, "2023-08-14T00:00:00+01:00")]
+  #[case(Some(Period::Month), None, "2023-08-01T00:00:00+01:00")]
+  #[case(Some(Period::Quarter), None, "2023-07-01T00:00:00+01:00")]
+  #[case(Some(Period::Year), None, "2023-01-01T00:00:00+00:00")]
+  #[case(None, Some(Period::Day), "2023-08-17T23:59:59.999999999+01:00")]
+  #[case(None, Some(Period::Week), "2023-08-20T23:59:59.999999999+01:00")]
+  #[case(None, Some(Period::Month), "2023-08-31T23:59:59.999999999+01:00")]
+  #[case(None, Some(Period::Quarter), "2023-09-30T23:59:59.999999999+01:00")]
+  #[case(None, Some(Period::Year), "2023-12-31T23:59:59.999999999+00:00")]
+  fn bounds(
+    #[case] start_of: Option<Period>,
+    #[case] end_of: Option<Period>,
+    #[case] expected: &str,
+  ) {
+    let args = SnapArgs {
+      next: None,
+      prev: None,
+      midnight: false,
+      start_of,
+      end_of,
+    };
+    let dt = Zone::from(Tz::Europe__London)
+      .with_ymd_and_hms(2023, 8, 17, 15, 30, 0)
+      .unwrap();
+    assert_eq!(
+      args.apply(dt, WeekStart::Mon).map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
+}
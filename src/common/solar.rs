@@ -0,0 +1,66 @@
+use chrono::{DateTime, Duration, Offset, TimeZone};
+use clap::Args;
+
+use crate::hduration::HDuration;
+
+#[derive(Args)]
+pub struct SolarArgs {
+  /// Add a column with the local mean solar time at this longitude, in
+  /// degrees east, and how far it is from the clock time
+  #[arg(long, allow_negative_numbers = true, value_parser = parse_longitude)]
+  longitude: Option<f64>,
+}
+
+fn parse_longitude(s: &str) -> Result<f64, String> {
+  s.parse::<f64>()
+    .ok()
+    .filter(|l| (-180.0..=180.0).contains(l))
+    .ok_or_else(|| format!("Longitude must be between -180 and 180, got: {}", s))
+}
+
+impl SolarArgs {
+  /// The solar time column for the given time, if a longitude was given
+  pub fn column<T: TimeZone>(&self, dt: &DateTime<T>) -> Option<String> {
+    let (solar, offset) = solar_time(dt, self.longitude?);
+    Some(format!("solar {} ({})", solar, HDuration::from(offset)))
+  }
+}
+
+/// The local mean solar time, to the second, and how far ahead of the clock
+/// it is. The sun crosses the meridian 4 minutes later per degree west
+fn solar_time<T: TimeZone>(dt: &DateTime<T>, longitude: f64) -> (String, Duration) {
+  let sun = Duration::seconds((longitude * 240.0).round() as i64);
+  let clock = Duration::seconds(dt.offset().fix().local_minus_utc() as i64);
+  let solar = dt.naive_utc() + sun;
+  (solar.format("%H:%M:%S").to_string(), sun - clock)
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{TimeZone, Utc};
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::{parse_longitude, solar_time};
+
+  #[rstest]
+  // New York, where the clock runs ahead of the sun
+  #[case(Tz::America__New_York, -74.006, "15:37:41", -3361)]
+  // Madrid is a zone further east than the sun would put it
+  #[case(Tz::Europe__Madrid, -3.7038, "20:18:53", -4489)]
+  #[case(Tz::UTC, 0.0, "20:33:42", 0)]
+  fn solar(#[case] tz: Tz, #[case] lon: f64, #[case] exp: &str, #[case] exp_offset: i64) {
+    let dt = Utc.timestamp_opt(1679258022, 0).unwrap().with_timezone(&tz);
+    let (solar, offset) = solar_time(&dt, lon);
+    assert_eq!((solar.as_str(), offset.num_seconds()), (exp, exp_offset))
+  }
+
+  #[rstest]
+  #[case("-74.006", true)]
+  #[case("180", true)]
+  #[case("181", false)]
+  #[case("east", false)]
+  fn longitude(#[case] input: &str, #[case] ok: bool) {
+    assert_eq!(parse_longitude(input).is_ok(), ok)
+  }
+}
@@ -1,8 +1,119 @@
 use std::str::FromStr;
 
-use chrono_tz::Tz;
+use chrono::{DateTime, Offset, TimeZone, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
 use clap::Args;
 
+use super::{Dst, Zone};
+
+/// Casual names for zones, which may map to several zones when ambiguous.
+/// Names IANA already defines, like EST and CET, resolve to those zones
+const ALIASES: &[(&str, &[Tz])] = &[
+  ("nyc", &[Tz::America__New_York]),
+  ("ny", &[Tz::America__New_York]),
+  ("la", &[Tz::America__Los_Angeles]),
+  ("sf", &[Tz::America__Los_Angeles]),
+  ("dc", &[Tz::America__New_York]),
+  ("pst", &[Tz::America__Los_Angeles]),
+  ("pdt", &[Tz::America__Los_Angeles]),
+  ("mdt", &[Tz::America__Denver]),
+  (
+    "cst",
+    &[
+      Tz::America__Chicago,
+      Tz::Asia__Shanghai,
+      Tz::America__Havana,
+    ],
+  ),
+  ("cdt", &[Tz::America__Chicago]),
+  ("edt", &[Tz::America__New_York]),
+  ("akst", &[Tz::America__Anchorage]),
+  ("bst", &[Tz::Europe__London, Tz::Asia__Dhaka]),
+  ("cest", &[Tz::Europe__Paris, Tz::Europe__Berlin]),
+  ("eest", &[Tz::Europe__Athens, Tz::Europe__Helsinki]),
+  (
+    "ist",
+    &[Tz::Asia__Kolkata, Tz::Europe__Dublin, Tz::Asia__Jerusalem],
+  ),
+  ("jst", &[Tz::Asia__Tokyo]),
+  ("kst", &[Tz::Asia__Seoul]),
+  ("aest", &[Tz::Australia__Sydney, Tz::Australia__Brisbane]),
+  ("sgt", &[Tz::Asia__Singapore]),
+  ("bangalore", &[Tz::Asia__Kolkata]),
+  ("bengaluru", &[Tz::Asia__Kolkata]),
+  ("mumbai", &[Tz::Asia__Kolkata]),
+  ("delhi", &[Tz::Asia__Kolkata]),
+  ("beijing", &[Tz::Asia__Shanghai]),
+  ("san francisco", &[Tz::America__Los_Angeles]),
+  ("seattle", &[Tz::America__Los_Angeles]),
+  ("boston", &[Tz::America__New_York]),
+  ("washington", &[Tz::America__New_York]),
+  ("austin", &[Tz::America__Chicago]),
+  ("dallas", &[Tz::America__Chicago]),
+];
+
+/// Finds the zones a place string could refer to, trying IANA names, then
+/// the alias table, then the city portion of IANA names. Zones that always
+/// agree are listed once
+pub fn place_candidates(place: &str) -> Vec<Tz> {
+  if let Ok(tz) = Tz::from_str_insensitive(place) {
+    return vec![tz];
+  }
+  let needle = place.trim().to_lowercase();
+  if let Some((_, zones)) = ALIASES.iter().find(|(alias, _)| *alias == needle) {
+    let mut found: Vec<Tz> = Vec::new();
+    for tz in zones.iter() {
+      if !found.iter().any(|f| equivalent(f, tz)) {
+        found.push(*tz);
+      }
+    }
+    return found;
+  }
+  let needle = needle.replace(' ', "_");
+  let mut found: Vec<Tz> = Vec::new();
+  let cities = TZ_VARIANTS.iter().filter(|tz| {
+    let name = tz.name();
+    let city = name.rsplit('/').next().unwrap_or(name);
+    name.contains('/') && city.to_lowercase() == needle
+  });
+  // Legacy links like America/Buenos_Aires share a city with their canonical
+  // zone, so only keep the most specific name among equivalent zones
+  for tz in cities {
+    match found.iter_mut().find(|f| equivalent(f, tz)) {
+      Some(f) if tz.name().len() > f.name().len() => *f = *tz,
+      Some(_) => {}
+      None => found.push(*tz),
+    }
+  }
+  found
+}
+
+/// Whether two zones agree on their offsets across a sample of instants
+fn equivalent(a: &Tz, b: &Tz) -> bool {
+  [0, 15638400, 946684800, 962409600, 1672531200, 1688169600]
+    .iter()
+    .filter_map(|ts| Utc.timestamp_opt(*ts, 0).single())
+    .map(|dt| dt.naive_utc())
+    .all(|dt| a.offset_from_utc_datetime(&dt).fix() == b.offset_from_utc_datetime(&dt).fix())
+}
+
+/// Resolves a place string into exactly one zone, listing the candidates when ambiguous
+pub fn resolve_place(place: &str) -> Result<Tz, String> {
+  match place_candidates(place).as_slice() {
+    [] => Err(format!("{} is not a known timezone", place)),
+    [tz] => Ok(*tz),
+    many => Err(format!(
+      "{} is ambiguous, could be: {}",
+      place,
+      many
+        .iter()
+        .map(|tz| tz.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+    )),
+  }
+}
+
 #[derive(Clone)]
 pub struct AutoTz(pub Tz);
 
@@ -15,23 +126,111 @@ impl FromStr for AutoTz {
     } else {
       s.to_string()
     };
-    pstr
-      .parse::<Tz>()
-      .map(AutoTz)
-      .map_err(|_| format!("{} is not a known timezone", s))
+    resolve_place(&pstr).map(AutoTz)
   }
 }
 
 #[derive(Args)]
 pub struct AtTimezoneArgs {
-  /// Convert to the given timezone. Omission will retain UTC. Accepts IANA names.
-  /// passing -t alone will use the system local timezone
-  #[arg(long, short='t', default_missing_value="local", require_equals=true, num_args=0..=1)]
-  at_timezone: Option<AutoTz>,
+  /// Convert to the given timezone, defaulting to $EPC_TIMEZONE and otherwise
+  /// UTC. Accepts IANA names, city names, and common aliases like NYC or PST.
+  /// Passing -t alone will use the system local timezone, and fixed offsets
+  /// like +05:30 or UTC-7 work too. Convert and current print every time once
+  /// per zone when repeated, while other commands use the first
+  #[arg(long, short='t', default_missing_value="local", require_equals=true, num_args=0..=1, env="EPC_TIMEZONE")]
+  at_timezone: Vec<Zone>,
+
+  /// Output in UTC, overriding -t and any default timezone
+  #[arg(long, short = 'U')]
+  utc: bool,
 }
 
 impl AtTimezoneArgs {
-  pub fn get(&self) -> Tz {
-    self.at_timezone.as_ref().map(|v| v.0).unwrap_or(Tz::UTC)
+  pub fn get(&self) -> Zone {
+    self.all()[0]
+  }
+
+  /// Whether the output zone was chosen, rather than left to default to UTC
+  pub fn given(&self) -> bool {
+    self.utc || !self.at_timezone.is_empty()
+  }
+
+  /// Every zone given, in order, or just UTC when none were
+  pub fn all(&self) -> Vec<Zone> {
+    match self.at_timezone.as_slice() {
+      _ if self.utc => vec![Zone::Named(Tz::UTC)],
+      [] => vec![Zone::Named(Tz::UTC)],
+      zones => zones.to_vec(),
+    }
+  }
+}
+
+#[derive(Args)]
+pub struct InputTimezoneArgs {
+  /// Read offset-less inputs like 2023-03-19T16:33:42, and relative ones like
+  /// yesterday, in this zone instead of the output timezone
+  #[arg(long)]
+  input_timezone: Option<Zone>,
+
+  /// How to read offset-less inputs that DST repeats or skips. Without it,
+  /// repeated times are read as their first occurrence and skipped ones fail
+  #[arg(value_enum, long)]
+  pub dst: Option<Dst>,
+}
+
+impl InputTimezoneArgs {
+  /// The time inputs are resolved against, given the one in the output zone
+  pub fn anchor(&self, now: &DateTime<Zone>) -> DateTime<Zone> {
+    match self.input_timezone {
+      None => *now,
+      Some(zone) => now.with_timezone(&zone),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::resolve_place;
+
+  #[rstest]
+  #[case("America/New_York", Tz::America__New_York)]
+  #[case("europe/paris", Tz::Europe__Paris)]
+  #[case("Paris", Tz::Europe__Paris)]
+  #[case("NYC", Tz::America__New_York)]
+  #[case("PST", Tz::America__Los_Angeles)]
+  // IANA's own abbreviations win over aliases, keeping EST a fixed -05:00
+  #[case("EST", Tz::EST)]
+  #[case("MST", Tz::MST)]
+  #[case("cet", Tz::CET)]
+  // Aliases naming zones that always agree are not ambiguous
+  #[case("CEST", Tz::Europe__Paris)]
+  #[case("new york", Tz::America__New_York)]
+  #[case("Buenos Aires", Tz::America__Argentina__Buenos_Aires)]
+  fn resolves(#[case] place: &str, #[case] expected: Tz) {
+    assert_eq!(resolve_place(place), Ok(expected))
+  }
+
+  #[rstest]
+  #[case(
+    "IST",
+    "IST is ambiguous, could be: Asia/Kolkata, Europe/Dublin, Asia/Jerusalem"
+  )]
+  #[case(
+    "aest",
+    "aest is ambiguous, could be: Australia/Sydney, Australia/Brisbane"
+  )]
+  fn ambiguous(#[case] place: &str, #[case] expected: &str) {
+    assert_eq!(resolve_place(place), Err(expected.into()));
+  }
+
+  #[test]
+  fn unknown() {
+    assert_eq!(
+      resolve_place("Atlantis"),
+      Err("Atlantis is not a known timezone".into())
+    );
   }
 }
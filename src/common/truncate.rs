@@ -1,65 +1,433 @@
-use chrono::{DateTime, Datelike, Duration, DurationRound, FixedOffset};
-use clap::Args;
+use chrono::{
+  DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone,
+};
+use clap::{builder::PossibleValue, Args, ValueEnum};
 
-use super::Precision;
+use super::{dst::relocal, Precision, WeekStart, Zone};
+
+/// The unit truncation moves time to the start of, which may be coarser than
+/// any precision
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TruncUnit {
+  Century,
+  Decade,
+  Year,
+  Quarter,
+  Month,
+  Field(Precision),
+}
+
+impl TruncUnit {
+  /// The length of calendar units in months
+  fn months(&self) -> Option<i64> {
+    match self {
+      TruncUnit::Century => Some(1200),
+      TruncUnit::Decade => Some(120),
+      TruncUnit::Year => Some(12),
+      TruncUnit::Quarter => Some(3),
+      TruncUnit::Month => Some(1),
+      TruncUnit::Field(_) => None,
+    }
+  }
+}
+
+impl ValueEnum for TruncUnit {
+  fn value_variants<'a>() -> &'a [Self] {
+    &[
+      TruncUnit::Century,
+      TruncUnit::Decade,
+      TruncUnit::Year,
+      TruncUnit::Quarter,
+      TruncUnit::Month,
+      TruncUnit::Field(Precision::Weeks),
+      TruncUnit::Field(Precision::Days),
+      TruncUnit::Field(Precision::Hours),
+      TruncUnit::Field(Precision::Mins),
+      TruncUnit::Field(Precision::Secs),
+      TruncUnit::Field(Precision::Millis),
+      TruncUnit::Field(Precision::Micros),
+      TruncUnit::Field(Precision::Nanos),
+    ]
+  }
+
+  fn to_possible_value(&self) -> Option<PossibleValue> {
+    match self {
+      TruncUnit::Century => {
+        Some(PossibleValue::new("century").help("Start of the century, such as 1900-01-01"))
+      }
+      TruncUnit::Decade => {
+        Some(PossibleValue::new("decade").help("Start of the decade, such as 1990-01-01"))
+      }
+      TruncUnit::Year => {
+        Some(PossibleValue::new("year").help("Start of the year, such as 1995-01-01"))
+      }
+      TruncUnit::Quarter => {
+        Some(PossibleValue::new("quarter").help("Start of the quarter, such as 1995-04-01"))
+      }
+      TruncUnit::Month => {
+        Some(PossibleValue::new("month").help("Start of the month, such as 1995-05-01"))
+      }
+      TruncUnit::Field(p) => p.to_possible_value(),
+    }
+  }
+}
 
 #[derive(Args)]
 pub struct TruncateArgs {
-  /// Truncate time starting from the given position onwards. Truncation
-  /// specifically means zeroing the epoch timestamp from that precision
-  /// onwards. This makes certain assumptions, like there being 7 days
-  /// in a week, and 52 weeks in a year. Not all of these
-  /// properties are globally true.
+  /// Truncate time to the start of the given unit, such as the start of the
+  /// hour for hours
   #[arg(value_enum, long, short = 'u')]
-  truncate: Option<Precision>,
+  truncate: Option<TruncUnit>,
+
+  /// Round time to the nearest boundary of the given unit rather than
+  /// truncating, with times halfway between rounding up
+  #[arg(value_enum, long, conflicts_with = "truncate")]
+  round: Option<Precision>,
+
+  /// Move time up to the next boundary of the given unit, leaving times
+  /// already on one alone
+  #[arg(value_enum, long, conflicts_with_all = ["truncate", "round"])]
+  ceil: Option<Precision>,
 }
 
 impl TruncateArgs {
-  pub fn apply(&self, dt: DateTime<FixedOffset>) -> Result<DateTime<FixedOffset>, String> {
-    let Some(field) = self.truncate.as_ref() else {
+  /// Truncates, rounds, or moves up the time as read in its own timezone, so
+  /// days start at its local midnight
+  pub fn apply(&self, dt: DateTime<Zone>, week_start: WeekStart) -> Result<DateTime<Zone>, String> {
+    let local = dt.naive_local();
+    let moved = self.apply_local(local, week_start)?;
+    if moved == local {
       return Ok(dt);
-    };
+    }
+    // Within an hour DST repeats, stay on the same side of the change
+    match dt.timezone().from_local_datetime(&moved) {
+      LocalResult::Ambiguous(_, second) if second.offset().fix() == dt.offset().fix() => Ok(second),
+      LocalResult::Ambiguous(first, _) => Ok(first),
+      _ => relocal(&dt, &moved),
+    }
+  }
 
-    let trunc_dur = match field {
-      Precision::Weeks | Precision::Days | Precision::Hours => Duration::days(1),
-      Precision::Mins => Duration::hours(1),
-      Precision::Secs => Duration::minutes(1),
-      Precision::Millis => Duration::seconds(1),
-      Precision::Nanos => Duration::milliseconds(1),
+  fn apply_local(
+    &self,
+    local: NaiveDateTime,
+    week_start: WeekStart,
+  ) -> Result<NaiveDateTime, String> {
+    if let Some(unit) = self.round {
+      let (lo, hi) = around(local, unit, week_start).ok_or("Could not round: out of range")?;
+      return Ok(if local - lo < hi - local { lo } else { hi });
+    }
+    if let Some(unit) = self.ceil {
+      let (_, hi) = around(local, unit, week_start).ok_or("Could not ceil: out of range")?;
+      return Ok(hi);
+    }
+    let Some(unit) = self.truncate else {
+      return Ok(local);
     };
-    let trunc = dt
-      .duration_trunc(trunc_dur)
-      .map_err(|e| format!("Could not truncate: {}", e))?;
-    let trunc = match field {
-      Precision::Weeks => trunc.with_month(1).and_then(|v| v.with_day(1)),
-      Precision::Days => trunc.with_day(1),
-      _ => Some(trunc),
+    let Some(months) = unit.months() else {
+      let TruncUnit::Field(p) = unit else {
+        unreachable!("only calendar units lack a fixed length");
+      };
+      return around(local, p, week_start)
+        .map(|(lo, _)| lo)
+        .ok_or_else(|| "Could not truncate: out of range".into());
     };
-    trunc.ok_or_else(|| "Failed to truncate weeks/days".into())
+    // Count months from year zero so units stay aligned before it too
+    let day = floor(local, Duration::days(1)).ok_or("Could not truncate: out of range")?;
+    let index = (day.year() as i64 * 12 + day.month0() as i64).div_euclid(months) * months;
+    day
+      .with_day(1)
+      .and_then(|v| v.with_month(index.rem_euclid(12) as u32 + 1))
+      .and_then(|v| v.with_year(index.div_euclid(12) as i32))
+      .ok_or_else(|| "Failed to truncate to a calendar boundary".into())
   }
 }
 
+/// Floors the local time to a multiple of the unit since 1970, which unlike
+/// chrono's `duration_trunc` also holds within one unit of the epoch
+fn floor(local: NaiveDateTime, unit: Duration) -> Option<NaiveDateTime> {
+  let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?.and_hms_opt(0, 0, 0)?;
+  let since = local - epoch;
+  let secs = since.num_seconds();
+  let nanos =
+    secs as i128 * 1_000_000_000 + (since - Duration::seconds(secs)).num_nanoseconds()? as i128;
+  let excess = nanos.rem_euclid(unit.num_nanoseconds()? as i128);
+  Some(local - Duration::nanoseconds(excess as i64))
+}
+
+/// The boundaries of the unit either side of the local time, which are equal
+/// when it already falls on one
+fn around(
+  local: NaiveDateTime,
+  unit: Precision,
+  week_start: WeekStart,
+) -> Option<(NaiveDateTime, NaiveDateTime)> {
+  let lo = match unit {
+    Precision::Weeks => {
+      let day = floor(local, Duration::days(1))?;
+      day - Duration::days(week_start.days_into(day.weekday()) as i64)
+    }
+    _ => floor(local, Duration::nanoseconds(unit.nanos_per()))?,
+  };
+  let hi = match lo == local {
+    true => lo,
+    false => lo.checked_add_signed(Duration::nanoseconds(unit.nanos_per()))?,
+  };
+  Some((lo, hi))
+}
+
 #[cfg(test)]
 mod test {
   use rstest::*;
 
-  use crate::common::{Precision, TruncateArgs};
+  use chrono::DateTime;
+  use chrono_tz::Tz;
+
+  use super::TruncUnit;
+  use crate::common::{Precision, TruncateArgs, WeekStart, Zone};
+
+  fn fixed(s: &str) -> DateTime<Zone> {
+    let dt = DateTime::parse_from_rfc3339(s).unwrap();
+    dt.with_timezone(&Zone::Fixed(*dt.offset()))
+  }
 
   #[rstest]
-  #[case(1681330711220000120, Precision::Nanos, 1681330711220000000)]
-  #[case(1681330711220000120, Precision::Millis, 1681330711000000000)]
-  #[case(1681330711220000120, Precision::Secs, 1681330680000000000)]
-  #[case(1681330711220000120, Precision::Mins, 1681329600000000000)]
-  #[case(1681330711220000120, Precision::Hours, 1681257600000000000)]
-  #[case(1681330711220000120, Precision::Days, 1680307200000000000)]
-  #[case(1681330711220000120, Precision::Weeks, 1672531200000000000)]
-  fn apply(#[case] in_nanos: i64, #[case] pre: Precision, #[case] exp_nanos: i64) {
+  #[case(
+    1681330711220123120,
+    TruncUnit::Field(Precision::Micros),
+    1681330711220123000
+  )]
+  #[case(
+    1681330711220123120,
+    TruncUnit::Field(Precision::Millis),
+    1681330711220000000
+  )]
+  #[case(
+    1681330711220000120,
+    TruncUnit::Field(Precision::Micros),
+    1681330711220000000
+  )]
+  #[case(
+    1681330711220000120,
+    TruncUnit::Field(Precision::Secs),
+    1681330711000000000
+  )]
+  #[case(
+    1681330711220000120,
+    TruncUnit::Field(Precision::Mins),
+    1681330680000000000
+  )]
+  #[case(
+    1681330711220000120,
+    TruncUnit::Field(Precision::Hours),
+    1681329600000000000
+  )]
+  #[case(
+    1681330711220000120,
+    TruncUnit::Field(Precision::Days),
+    1681257600000000000
+  )]
+  #[case(1681330711220000120, TruncUnit::Month, 1680307200000000000)]
+  #[case(
+    1681330711220000120,
+    TruncUnit::Field(Precision::Weeks),
+    1681084800000000000
+  )]
+  #[case(1681330711220000120, TruncUnit::Quarter, 1680307200000000000)]
+  #[case(1681330711220000120, TruncUnit::Year, 1672531200000000000)]
+  // Before 1970 truncation still moves towards the past
+  #[case(-1679258022123456789, TruncUnit::Field(Precision::Micros), -1679258022123457000)]
+  #[case(-1679258022123456789, TruncUnit::Field(Precision::Secs), -1679258023000000000)]
+  #[case(-1679258022123456789, TruncUnit::Field(Precision::Mins), -1679258040000000000)]
+  #[case(-1679258022123456789, TruncUnit::Field(Precision::Days), -1679270400000000000)]
+  #[case(-1679258022123456789, TruncUnit::Month, -1680480000000000000)]
+  #[case(-1679258022123456789, TruncUnit::Year, -1704153600000000000)]
+  #[case(-5000000000, TruncUnit::Field(Precision::Mins), -60000000000)]
+  #[case(-1, TruncUnit::Field(Precision::Micros), -1000)]
+  fn apply(#[case] in_nanos: i64, #[case] unit: TruncUnit, #[case] exp_nanos: i64) {
     let args = TruncateArgs {
-      truncate: Some(pre),
+      truncate: Some(unit),
+      round: None,
+      ceil: None,
     };
     let nanos = Precision::Nanos;
-    let truncated_0 = args.apply(nanos.parse(in_nanos).unwrap().into());
-    let truncated = truncated_0.map(|p| p.timestamp_nanos());
+    let utc = nanos.parse(in_nanos).unwrap();
+    let truncated_0 = args.apply(utc.with_timezone(&Zone::from(Tz::UTC)), WeekStart::Mon);
+    let truncated = truncated_0.and_then(|p| nanos.as_stamp(&p));
     assert_eq!(truncated, Ok(exp_nanos))
   }
+
+  #[rstest]
+  #[case("2023-04-12T20:18:31Z", TruncUnit::Decade, "2020-01-01T00:00:00+00:00")]
+  #[case(
+    "2023-04-12T20:18:31Z",
+    TruncUnit::Century,
+    "2000-01-01T00:00:00+00:00"
+  )]
+  #[case(
+    "1899-12-31T23:59:59-05:00",
+    TruncUnit::Century,
+    "1800-01-01T00:00:00-05:00"
+  )]
+  #[case("1969-07-20T20:17:40Z", TruncUnit::Decade, "1960-01-01T00:00:00+00:00")]
+  #[case(
+    "1969-07-20T20:17:40Z",
+    TruncUnit::Quarter,
+    "1969-07-01T00:00:00+00:00"
+  )]
+  fn calendar(#[case] input: &str, #[case] unit: TruncUnit, #[case] expected: &str) {
+    let args = TruncateArgs {
+      truncate: Some(unit),
+      round: None,
+      ceil: None,
+    };
+    let dt = fixed(input);
+    assert_eq!(
+      args.apply(dt, WeekStart::Mon).map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
+
+  #[rstest]
+  #[case(
+    "2023-04-12T14:37:00+02:00",
+    Precision::Hours,
+    "2023-04-12T15:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T14:29:59+02:00",
+    Precision::Hours,
+    "2023-04-12T14:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T14:30:00+02:00",
+    Precision::Hours,
+    "2023-04-12T15:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T11:59:00+02:00",
+    Precision::Days,
+    "2023-04-12T00:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T12:00:00+02:00",
+    Precision::Days,
+    "2023-04-13T00:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T14:37:00+02:00",
+    Precision::Weeks,
+    "2023-04-10T00:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-14T00:00:00+02:00",
+    Precision::Weeks,
+    "2023-04-17T00:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T14:37:00.5+02:00",
+    Precision::Secs,
+    "2023-04-12T14:37:01+02:00"
+  )]
+  #[case("1969-12-31T23:59:59.4Z", Precision::Secs, "1969-12-31T23:59:59+00:00")]
+  fn round(#[case] input: &str, #[case] unit: Precision, #[case] expected: &str) {
+    let args = TruncateArgs {
+      truncate: None,
+      round: Some(unit),
+      ceil: None,
+    };
+    let dt = fixed(input);
+    assert_eq!(
+      args.apply(dt, WeekStart::Mon).map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
+
+  #[rstest]
+  #[case(
+    "2023-04-12T14:01:00+02:00",
+    Precision::Hours,
+    "2023-04-12T15:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T14:00:00+02:00",
+    Precision::Hours,
+    "2023-04-12T14:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T00:00:00.001+02:00",
+    Precision::Days,
+    "2023-04-13T00:00:00+02:00"
+  )]
+  #[case(
+    "2023-04-12T14:37:00+02:00",
+    Precision::Weeks,
+    "2023-04-17T00:00:00+02:00"
+  )]
+  fn ceil(#[case] input: &str, #[case] unit: Precision, #[case] expected: &str) {
+    let args = TruncateArgs {
+      truncate: None,
+      round: None,
+      ceil: Some(unit),
+    };
+    let dt = fixed(input);
+    assert_eq!(
+      args.apply(dt, WeekStart::Mon).map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
+
+  #[rstest]
+  // 2023-04-12 is a Wednesday
+  #[case(WeekStart::Mon, "2023-04-10T00:00:00+02:00")]
+  #[case(WeekStart::Sun, "2023-04-09T00:00:00+02:00")]
+  fn weeks_from(#[case] week_start: WeekStart, #[case] expected: &str) {
+    let args = TruncateArgs {
+      truncate: Some(TruncUnit::Field(Precision::Weeks)),
+      round: None,
+      ceil: None,
+    };
+    let dt = fixed("2023-04-12T14:37:00+02:00");
+    assert_eq!(
+      args.apply(dt, week_start).map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
+
+  #[rstest]
+  // New York falls back from 01:59:59 EDT to 01:00:00 EST
+  #[case(
+    "2023-11-05T01:30:45-05:00",
+    TruncUnit::Field(Precision::Mins),
+    "2023-11-05T01:30:00-05:00"
+  )]
+  #[case(
+    "2023-11-05T01:30:45-04:00",
+    TruncUnit::Field(Precision::Mins),
+    "2023-11-05T01:30:00-04:00"
+  )]
+  #[case(
+    "2023-11-05T15:00:00-05:00",
+    TruncUnit::Field(Precision::Days),
+    "2023-11-05T00:00:00-04:00"
+  )]
+  #[case(
+    "2023-03-12T15:00:00-04:00",
+    TruncUnit::Field(Precision::Days),
+    "2023-03-12T00:00:00-05:00"
+  )]
+  fn local(#[case] input: &str, #[case] unit: TruncUnit, #[case] expected: &str) {
+    let args = TruncateArgs {
+      truncate: Some(unit),
+      round: None,
+      ceil: None,
+    };
+    let dt = DateTime::parse_from_rfc3339(input).unwrap();
+    let tz = Zone::from(Tz::America__New_York);
+    assert_eq!(
+      args
+        .apply(dt.with_timezone(&tz), WeekStart::Mon)
+        .map(|dt| dt.to_rfc3339()),
+      Ok(expected.into())
+    )
+  }
 }
@@ -0,0 +1,35 @@
+use chrono::Weekday;
+use clap::{Args, ValueEnum};
+
+/// The first day of the week
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum WeekStart {
+  /// Monday, as ISO 8601 weeks do
+  Mon,
+  /// Sunday
+  Sun,
+}
+
+impl WeekStart {
+  /// How many days the weekday falls after the start of its week
+  pub fn days_into(&self, day: Weekday) -> u32 {
+    match self {
+      WeekStart::Mon => day.num_days_from_monday(),
+      WeekStart::Sun => day.num_days_from_sunday(),
+    }
+  }
+}
+
+#[derive(Args)]
+pub struct WeekStartArgs {
+  /// The day weeks start on for week based truncation, periods, and calendars.
+  /// Business days skip Saturday and Sunday whichever day this is
+  #[arg(value_enum, long, global = true, env = "EPC_WEEK_START", default_value_t = WeekStart::Mon)]
+  week_start: WeekStart,
+}
+
+impl WeekStartArgs {
+  pub fn get(&self) -> WeekStart {
+    self.week_start
+  }
+}
@@ -0,0 +1,171 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{FixedOffset, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+
+use super::AutoTz;
+
+/// A target timezone, either a named IANA zone or a fixed offset from UTC
+/// like +05:30, which the IANA database can't express for most offsets
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Zone {
+  Named(Tz),
+  Fixed(FixedOffset),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum ZoneOffset {
+  Named(<Tz as TimeZone>::Offset),
+  Fixed(FixedOffset),
+}
+
+impl From<Tz> for Zone {
+  fn from(tz: Tz) -> Self {
+    Zone::Named(tz)
+  }
+}
+
+impl Offset for ZoneOffset {
+  fn fix(&self) -> FixedOffset {
+    match self {
+      ZoneOffset::Named(o) => o.fix(),
+      ZoneOffset::Fixed(o) => *o,
+    }
+  }
+}
+
+impl Display for ZoneOffset {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ZoneOffset::Named(o) => o.fmt(f),
+      ZoneOffset::Fixed(o) => o.fmt(f),
+    }
+  }
+}
+
+impl Display for Zone {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Zone::Named(tz) => tz.fmt(f),
+      Zone::Fixed(o) => write!(f, "UTC{}", o),
+    }
+  }
+}
+
+impl TimeZone for Zone {
+  type Offset = ZoneOffset;
+
+  fn from_offset(offset: &ZoneOffset) -> Self {
+    match offset {
+      ZoneOffset::Named(o) => Zone::Named(Tz::from_offset(o)),
+      ZoneOffset::Fixed(o) => Zone::Fixed(*o),
+    }
+  }
+
+  fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<ZoneOffset> {
+    match self {
+      Zone::Named(tz) => tz.offset_from_local_date(local).map(ZoneOffset::Named),
+      Zone::Fixed(o) => LocalResult::Single(ZoneOffset::Fixed(*o)),
+    }
+  }
+
+  fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<ZoneOffset> {
+    match self {
+      Zone::Named(tz) => tz.offset_from_local_datetime(local).map(ZoneOffset::Named),
+      Zone::Fixed(o) => LocalResult::Single(ZoneOffset::Fixed(*o)),
+    }
+  }
+
+  fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+    match self {
+      Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_date(utc)),
+      Zone::Fixed(o) => ZoneOffset::Fixed(*o),
+    }
+  }
+
+  fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+    match self {
+      Zone::Named(tz) => ZoneOffset::Named(tz.offset_from_utc_datetime(utc)),
+      Zone::Fixed(o) => ZoneOffset::Fixed(*o),
+    }
+  }
+}
+
+/// Reads an offset like +05:30, -0700, or +5, optionally after UTC or GMT
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+  let upper = s.trim().to_uppercase();
+  let rest = ["UTC", "GMT"]
+    .iter()
+    .find_map(|p| upper.strip_prefix(p))
+    .unwrap_or(&upper);
+  let sign = match rest.chars().next()? {
+    '+' => 1,
+    '-' => -1,
+    _ => return None,
+  };
+  let digits = &rest[1..];
+  let (hours, minutes) = match digits.split_once(':') {
+    Some((h, m)) => (h, m),
+    None if digits.len() == 4 => digits.split_at(2),
+    None => (digits, "0"),
+  };
+  if hours.is_empty() || hours.len() > 2 || minutes.len() > 2 {
+    return None;
+  }
+  let (hours, minutes) = (hours.parse::<i32>().ok()?, minutes.parse::<i32>().ok()?);
+  if minutes >= 60 {
+    return None;
+  }
+  FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+impl FromStr for Zone {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match parse_offset(s) {
+      Some(offset) => Ok(Zone::Fixed(offset)),
+      None => s.parse::<AutoTz>().map(|AutoTz(tz)| Zone::Named(tz)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{FixedOffset, TimeZone, Utc};
+  use chrono_tz::Tz;
+  use rstest::*;
+
+  use super::Zone;
+
+  #[rstest]
+  #[case("+05:30", Zone::Fixed(FixedOffset::east_opt(19800).unwrap()))]
+  #[case("UTC-7", Zone::Fixed(FixedOffset::west_opt(25200).unwrap()))]
+  #[case("gmt+0545", Zone::Fixed(FixedOffset::east_opt(20700).unwrap()))]
+  #[case("-09:30", Zone::Fixed(FixedOffset::west_opt(34200).unwrap()))]
+  #[case("UTC", Zone::Named(Tz::UTC))]
+  #[case("Tokyo", Zone::Named(Tz::Asia__Tokyo))]
+  fn from_str(#[case] input: &str, #[case] expected: Zone) {
+    assert_eq!(input.parse::<Zone>(), Ok(expected))
+  }
+
+  #[rstest]
+  #[case("+25:00")]
+  #[case("+05:75")]
+  #[case("UTC+")]
+  fn invalid(#[case] input: &str) {
+    assert!(input.parse::<Zone>().is_err())
+  }
+
+  #[test]
+  fn formats() {
+    let zone = "+05:30".parse::<Zone>().unwrap();
+    let dt = Utc
+      .timestamp_opt(1679258022, 0)
+      .unwrap()
+      .with_timezone(&zone);
+    assert_eq!(dt.to_rfc3339(), "2023-03-20T02:03:42+05:30");
+    assert_eq!(dt.format("%Z").to_string(), "+05:30");
+    assert_eq!(zone.to_string(), "UTC+05:30");
+  }
+}
@@ -0,0 +1,120 @@
+use std::{
+  cmp::Ordering,
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::Utc;
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs},
+  hduration::HDuration,
+  Handler,
+};
+
+#[derive(Args)]
+pub struct CompareArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  // Both times are parsed in handle, as clap's usage errors exit 2 and
+  // would read as the first being later
+  /// The time being compared
+  #[arg(allow_negative_numbers = true)]
+  first: String,
+
+  /// The time to compare against
+  #[arg(allow_negative_numbers = true)]
+  second: String,
+}
+
+impl Handler for CompareArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let resolve = |s: &str| {
+      s.parse::<ConversionInput>()
+        .and_then(|inp| inp.to_dt(&self.format.precision, &now))
+    };
+    let resolved = resolve(&self.first).and_then(|a| Ok((a, resolve(&self.second)?)));
+    let (a, b) = match resolved {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(3)),
+      Ok(v) => v,
+    };
+
+    let (verdict, code) = match a.cmp(&b) {
+      Ordering::Equal => ("equal to", 0),
+      Ordering::Less => ("earlier than", 1),
+      Ordering::Greater => ("later than", 2),
+    };
+    let shown = self
+      .format
+      .format(&a.with_timezone(&into_tz))
+      .and_then(|a| Ok((a, self.format.format(&b.with_timezone(&into_tz))?)));
+    let (shown_a, shown_b) = match shown {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(3)),
+      Ok(v) => v,
+    };
+    let delta = HDuration::from(b - a);
+    let sign = if delta.negative || delta.inner.is_zero() {
+      ""
+    } else {
+      "+"
+    };
+    writeln!(
+      &mut out,
+      "{} is {} {} ({}{})",
+      shown_a, verdict, shown_b, sign, delta
+    )?;
+    Ok(ExitCode::from(code))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::process::ExitCode;
+
+  use crate::run_test_code;
+
+  #[test]
+  fn earlier() {
+    let (code, output, error) = run_test_code(" compare -p secs 1679258022 1679258186");
+    assert_eq!("", error);
+    assert_eq!("1679258022 is earlier than 1679258186 (+2m 44s)\n", output);
+    assert_eq!(ExitCode::from(1), code);
+  }
+
+  #[test]
+  fn later() {
+    let (code, output, error) =
+      run_test_code(" compare -f=%H:%M:%S 2023-03-19T16:36:26-04:00 2023-03-19T16:33:42-04:00");
+    assert_eq!("", error);
+    assert_eq!("20:36:26 is later than 20:33:42 (-2m 44s)\n", output);
+    assert_eq!(ExitCode::from(2), code);
+  }
+
+  #[test]
+  fn equal() {
+    let (code, output, error) =
+      run_test_code(" compare -p secs 1679258186 2023-03-19T16:36:26-04:00");
+    assert_eq!("", error);
+    assert_eq!("1679258186 is equal to 1679258186 (0s)\n", output);
+    assert_eq!(ExitCode::from(0), code);
+  }
+
+  #[test]
+  fn unresolvable() {
+    let (code, output, error) = run_test_code(" compare garbage 123");
+    assert_eq!("", output);
+    assert!(!error.is_empty());
+    assert_eq!(ExitCode::from(3), code);
+  }
+}
@@ -0,0 +1,144 @@
+use std::{env, ffi::OsString, fs, io, path::PathBuf};
+
+use serde::Deserialize;
+
+/// User preferences, read from $EPC_CONFIG or $XDG_CONFIG_HOME/epc/config.toml
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+  /// The subcommand arguments are handed to when they do not name one
+  default_command: Option<String>,
+  /// The arguments to run with when epc is invoked without any
+  bare: Option<Vec<String>>,
+  /// Defaults for -t, -p, -f, -o, --holidays, --fiscal-start, and
+  /// --week-start, which their flags and environment variables still override
+  timezone: Option<String>,
+  precision: Option<String>,
+  format: Option<String>,
+  order: Option<String>,
+  holidays: Option<String>,
+  fiscal_start: Option<String>,
+  week_start: Option<String>,
+}
+
+impl Config {
+  fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("EPC_CONFIG") {
+      return Some(path.into());
+    }
+    env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+      .map(|dir| dir.join("epc").join("config.toml"))
+  }
+
+  /// Reads the config file, which is optional
+  pub fn load() -> Result<Self, String> {
+    let Some(path) = Config::path() else {
+      return Ok(Config::default());
+    };
+    match fs::read_to_string(&path) {
+      Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+      Err(e) => Err(format!("Could not read {}: {}", path.display(), e)),
+      Ok(text) => {
+        toml::from_str(&text).map_err(|e| format!("Invalid config {}: {}", path.display(), e))
+      }
+    }
+  }
+
+  /// The environment variables backing each configured default
+  pub fn defaults(&self) -> Vec<(&'static str, &str)> {
+    [
+      ("EPC_TIMEZONE", &self.timezone),
+      ("EPC_PRECISION", &self.precision),
+      ("EPC_FORMAT", &self.format),
+      ("EPC_ORDER", &self.order),
+      ("EPC_HOLIDAYS", &self.holidays),
+      ("EPC_FISCAL_START", &self.fiscal_start),
+      ("EPC_WEEK_START", &self.week_start),
+    ]
+    .into_iter()
+    .filter_map(|(var, value)| value.as_deref().map(|v| (var, v)))
+    .collect()
+  }
+
+  /// Routes the arguments to the configured subcommands, given the names of
+  /// those that exist
+  pub fn route(&self, mut args: Vec<OsString>, subcommands: &[String]) -> Vec<OsString> {
+    if args.len() <= 1 {
+      if let Some(bare) = &self.bare {
+        args.extend(bare.iter().map(OsString::from));
+        return args;
+      }
+    }
+    let Some(default) = &self.default_command else {
+      return args;
+    };
+    let names_command = args.get(1).and_then(|a| a.to_str()).is_some_and(|a| {
+      subcommands.iter().any(|s| s == a) || ["-h", "--help", "-V", "--version"].contains(&a)
+    });
+    if !names_command {
+      args.insert(1.min(args.len()), default.into());
+    }
+    args
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::ffi::OsString;
+
+  use rstest::*;
+
+  use super::Config;
+
+  fn config(text: &str) -> Config {
+    toml::from_str(text).unwrap()
+  }
+
+  #[rstest]
+  #[case("", "epc", "epc")]
+  #[case("", "epc 123", "epc 123")]
+  #[case("default_command = \"convert\"", "epc 123 -f", "epc convert 123 -f")]
+  #[case("default_command = \"convert\"", "epc", "epc convert")]
+  #[case("default_command = \"convert\"", "epc delta 1 2", "epc delta 1 2")]
+  #[case("default_command = \"convert\"", "epc --help", "epc --help")]
+  #[case(
+    "bare = [\"timezone\", \"--resolve\", \"tokyo\"]",
+    "epc",
+    "epc timezone --resolve tokyo"
+  )]
+  #[case(
+    "bare = [\"current\"]\ndefault_command = \"convert\"",
+    "epc",
+    "epc current"
+  )]
+  #[case(
+    "bare = [\"current\"]\ndefault_command = \"convert\"",
+    "epc 1",
+    "epc convert 1"
+  )]
+  fn route(#[case] text: &str, #[case] args: &str, #[case] expected: &str) {
+    let subcommands = ["convert".to_string(), "delta".into(), "timezone".into()];
+    let args = args.split(' ').map(OsString::from).collect();
+    let routed = config(text).route(args, &subcommands);
+    assert_eq!(routed.join(&OsString::from(" ")), OsString::from(expected))
+  }
+
+  #[test]
+  fn defaults() {
+    let config = config("timezone = \"America/New_York\"\nprecision = \"secs\"");
+    assert_eq!(
+      vec![
+        ("EPC_TIMEZONE", "America/New_York"),
+        ("EPC_PRECISION", "secs")
+      ],
+      config.defaults()
+    );
+  }
+
+  #[test]
+  fn unknown_key() {
+    assert!(toml::from_str::<Config>("colour = true").is_err())
+  }
+}
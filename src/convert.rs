@@ -1,14 +1,18 @@
 use std::{
   cmp::Ordering,
+  collections::HashSet,
   io::{self, Write},
-  str::FromStr,
+  process::ExitCode,
 };
 
-use chrono::{DateTime, FixedOffset};
+use chrono::{Offset, SecondsFormat, Utc};
 use clap::{Args, ValueEnum};
 
 use crate::{
-  common::{AtTimezoneArgs, CalcArgs, FormatArgs, Precision, TruncateArgs},
+  common::{
+    named_scheme, AtTimezoneArgs, BoundsArgs, CalcArgs, CoarsenArgs, ConversionInput, FormatArgs,
+    InputTimezoneArgs, ShiftArgs, SnapArgs, SolarArgs, TruncateArgs, WeekStartArgs,
+  },
   Handler,
 };
 
@@ -25,46 +29,145 @@ pub struct ConvArgs {
   #[command(flatten)]
   timezone: AtTimezoneArgs,
 
+  #[command(flatten)]
+  input_timezone: InputTimezoneArgs,
+
   #[command(flatten)]
   format: FormatArgs,
 
+  #[command(flatten)]
+  snap: SnapArgs,
+
   #[command(flatten)]
   add: CalcArgs,
 
   #[command(flatten)]
   truncate: TruncateArgs,
 
-  /// Mixture of Epoch timestamps in the given precision or date-time strings
-  #[arg()]
+  #[command(flatten)]
+  week: WeekStartArgs,
+
+  #[command(flatten)]
+  solar: SolarArgs,
+
+  #[command(flatten)]
+  bounds: BoundsArgs,
+
+  #[command(flatten)]
+  shift: ShiftArgs,
+
+  #[command(flatten)]
+  coarsen: CoarsenArgs,
+
+  /// Only print inputs at or after this time
+  #[arg(long, allow_negative_numbers = true)]
+  after: Option<ConversionInput>,
+
+  /// Only print inputs before this time, or at it when bounds are closed
+  #[arg(long, allow_negative_numbers = true)]
+  before: Option<ConversionInput>,
+
+  /// Mixture of Epoch timestamps in the given precision, date-time strings, or
+  /// relative times like "yesterday", "last friday", and "2h ago". Inputs
+  /// carrying a zone, like 2025-03-30T02:30[Europe/Berlin] or git's
+  /// "1679258022 -0400", print in it unless -t is given
+  #[arg(allow_negative_numbers = true)]
   input: Vec<ConversionInput>,
 
   /// When supplying multiple timestamps what order to print them in
-  #[arg(value_enum, long, short)]
+  #[arg(value_enum, long, short, env = "EPC_ORDER")]
   order: Option<Order>,
+
+  /// Print each time encoded in this epoch scheme. Inputs take a built in
+  /// scheme or this one as name:value. Built in are filetime, ticks, cocoa,
+  /// gps, gps-week, jd, jdn, and mjd, while others are run as an
+  /// `epc-epoch-<name>` program on the PATH, taking `decode <value>` or
+  /// `encode <rfc3339>` and printing the other
+  #[arg(long)]
+  epoch: Option<String>,
+
+  /// Drop any time naming the same instant as one printed before it
+  #[arg(long)]
+  dedup: bool,
 }
 
 impl Handler for ConvArgs {
-  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<(), io::Error>
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
   where
     W: Write,
     E: Write,
   {
     let into_tz = self.timezone.get();
-    let maybe_datetimes = self
-      .input
-      .iter()
-      // Extract as datetime
-      .map(|inp| inp.to_dt(&self.format.precision))
-      .map(|rdt| rdt.and_then(|dt| self.truncate.apply(dt)))
-      // Convert to the given timezone
-      .map(|rdt| rdt.map(|dt| dt.with_timezone(&into_tz)))
-      // Apply addition
-      .map(|rdt| rdt.and_then(|dt| self.add.eval(dt)))
-      .collect::<Result<Vec<_>, _>>();
+    let now = self
+      .input_timezone
+      .anchor(&Utc::now().with_timezone(&into_tz));
+    let resolve = |inp: &Option<ConversionInput>| {
+      inp
+        .as_ref()
+        .map(|i| i.to_dt_with(&self.format.precision, &now, self.input_timezone.dst))
+        .transpose()
+    };
+    // Inputs naming their own zone are printed in it unless -t says otherwise
+    let zone_of = |inp: &ConversionInput| {
+      inp
+        .zone()
+        .filter(|_| !self.timezone.given())
+        .unwrap_or(into_tz)
+    };
+    let epoch = match self.epoch.as_deref().map(named_scheme).transpose() {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(epoch) => epoch,
+    };
+    // Only inputs naming the --epoch scheme may run it, built in or not
+    let to_dt = |inp: &ConversionInput| match (inp, &epoch, &self.epoch) {
+      (ConversionInput::Scheme { name, raw }, Some(epoch), Some(given))
+        if name.eq_ignore_ascii_case(given) =>
+      {
+        epoch.decode(raw).map(|dt| dt.with_timezone(&Utc.fix()))
+      }
+      _ => inp.to_dt_with(&self.format.precision, &now, self.input_timezone.dst),
+    };
+    let mut lost = Vec::new();
+    let mut cut = 0;
+    let window = resolve(&self.after).and_then(|a| Ok((a, resolve(&self.before)?)));
+    let maybe_datetimes = window.and_then(|(after, before)| {
+      self
+        .input
+        .iter()
+        // Extract as datetime
+        .map(|inp| (zone_of(inp), to_dt(inp)))
+        // Drop anything outside the window, leaving errors to surface
+        .filter(|(_, rdt)| {
+          rdt.as_ref().map_or(true, |dt| {
+            let started = after.is_none_or(|a| *dt >= a);
+            if started && before.is_some_and(|b| self.bounds.cuts(dt, &b)) {
+              cut += 1;
+            }
+            started && before.is_none_or(|b| self.bounds.short_of(dt, &b))
+          })
+        })
+        // Convert to the given timezone, then truncate in it
+        .map(|(zone, rdt)| rdt.map(|dt| dt.with_timezone(&zone)))
+        .map(|rdt| rdt.and_then(|dt| self.truncate.apply(dt, self.week.get())))
+        .map(|rdt| rdt.and_then(|dt| self.snap.apply(dt, self.week.get())))
+        // Apply addition, noting any time the undo command cannot restore
+        .map(|rdt| {
+          rdt.and_then(|dt| {
+            let added = self.add.eval(dt)?;
+            if !self.add.undoes(&dt, &added) {
+              lost.push(dt);
+            }
+            Ok(added)
+          })
+        })
+        .map(|rdt| rdt.and_then(|dt| self.shift.apply(dt)))
+        .map(|rdt| rdt.and_then(|dt| self.coarsen.apply(dt)))
+        .collect::<Result<Vec<_>, _>>()
+    });
 
     // Sus out any errors now that we're done oeprating
     let mut dts = match maybe_datetimes {
-      Err(e) => return writeln!(&mut err, "{}", e),
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
       Ok(dts) => dts,
     };
 
@@ -75,61 +178,74 @@ impl Handler for ConvArgs {
       None => Ordering::Equal,
     });
 
-    // Apply output formatting
-    dts
-      .iter()
-      .try_for_each(|dt| writeln!(&mut out, "{}", self.format.format(dt)))
-  }
-}
-
-#[derive(Clone)]
-enum ConversionInput {
-  Stamp(i64),
-  String(DateTime<FixedOffset>),
-}
-
-impl ConversionInput {
-  fn to_dt(&self, precision: &Precision) -> Result<DateTime<FixedOffset>, String> {
-    match self {
-      ConversionInput::String(dt) => Ok(*dt),
-      ConversionInput::Stamp(ts) => precision
-        .parse(*ts)
-        .single()
-        .map(|dt| dt.into())
-        .ok_or_else(|| format!("Could not parse: {}", ts)),
+    if self.dedup {
+      let mut seen = HashSet::new();
+      dts.retain(|dt| seen.insert(*dt));
     }
-  }
-}
-
-impl FromStr for ConversionInput {
-  type Err = String;
 
-  fn from_str(arg: &str) -> Result<Self, Self::Err> {
-    match arg.parse::<i64>() {
-      Ok(ts) => Ok(ConversionInput::Stamp(ts)),
-      Err(_) => match arg.parse::<DateTime<FixedOffset>>() {
-        Ok(dt) => Ok(ConversionInput::String(dt)),
-        Err(_) => Err(format!("Could not parse: {}", arg)),
-      },
+    // Apply output formatting
+    let mut code = ExitCode::SUCCESS;
+    for dt in &dts {
+      let zones = match self.timezone.given() {
+        true => self.timezone.all(),
+        false => vec![dt.timezone()],
+      };
+      for tz in &zones {
+        let dt = dt.with_timezone(tz);
+        let text = match &epoch {
+          None => self.format.format(&dt),
+          Some(epoch) => epoch.encode(&dt.with_timezone(&Utc)),
+        };
+        let text = match text {
+          Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+          Ok(text) => text,
+        };
+        match self.solar.column(&dt) {
+          Some(solar) => writeln!(&mut out, "{}\t{}", text, solar)?,
+          None => writeln!(&mut out, "{}", text)?,
+        }
+      }
+      if let Some(flag) = self.format.js_unsafe(dt) {
+        writeln!(&mut err, "{}", flag)?;
+        code = ExitCode::FAILURE;
+      }
+    }
+    if cut > 0 {
+      writeln!(
+        &mut err,
+        "Left out {} time{} at --before, as bounds are half-open",
+        cut,
+        if cut == 1 { "" } else { "s" }
+      )?;
+    }
+    if let Some(summary) = self.coarsen.summary(&dts) {
+      writeln!(&mut err, "{}", summary)?;
+    }
+    if let Some(undo) = self.add.inverse_command(&dts) {
+      writeln!(&mut err, "undo with: {}", undo)?;
+      if !lost.is_empty() {
+        let lost = lost
+          .iter()
+          .map(|dt| dt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+          .collect::<Vec<_>>()
+          .join(" ");
+        writeln!(
+          &mut err,
+          "which cannot restore {}, as months clamp to the end of shorter ones",
+          lost
+        )?;
+      }
     }
+    Ok(code)
   }
 }
 
 #[cfg(test)]
 mod test {
-  use crate::{run, Cli};
-  use clap::Parser;
-  use indoc::indoc;
+  use std::process::ExitCode;
 
-  fn run_test(cli_str: &str) -> (String, String) {
-    let mut output = Vec::new();
-    let mut error = Vec::new();
-    let cli = Cli::try_parse_from(cli_str.split(' ')).expect("Could not parse args");
-    run(cli, &mut output, &mut error).expect("Failed to run");
-    let output = String::from_utf8(output).expect("Not UTF-8");
-    let error = String::from_utf8(error).expect("Not UTF-8");
-    (output, error)
-  }
+  use crate::{run_test, run_test_code, Cli};
+  use indoc::indoc;
 
   #[test]
   fn verify_cli() {
@@ -194,6 +310,183 @@ mod test {
     );
   }
 
+  #[test]
+  fn dedup() {
+    let (output, error) = run_test(
+      " convert -p secs --dedup 1679258186 1679258022 2023-03-19T16:36:26-04:00 1679258022",
+    );
+    assert_eq!("", error);
+    assert_eq!("1679258186\n1679258022\n", output);
+  }
+
+  #[test]
+  fn window() {
+    let (output, error) = run_test(
+      " convert -p secs --after 2023-03-19T20:33:42Z --before 1679258186 1679258021 1679258022 1679258100 1679258186",
+    );
+    assert_eq!("", error);
+    assert_eq!("1679258022\n1679258100\n1679258186\n", output);
+
+    let (output, error) = run_test(
+      " convert -p secs --bounds half-open --after 1679258022 --before 1679258186 1679258022 1679258186",
+    );
+    assert_eq!(
+      "Left out 1 time at --before, as bounds are half-open\n",
+      error
+    );
+    assert_eq!("1679258022\n", output);
+  }
+
+  #[test]
+  fn shift_secret() {
+    let (output, error) = run_test(" convert -p secs --shift-secret=-1h 1679258022 1679258186");
+    assert_eq!("", error);
+    assert_eq!("1679254422\n1679254586\n", output);
+
+    let (output, _) = run_test(" convert -p secs --shift-secret hunter2 1679258022 1679258186");
+    let shifted = output
+      .lines()
+      .map(|l| l.parse::<i64>().unwrap())
+      .collect::<Vec<_>>();
+    assert_ne!(shifted[0], 1679258022);
+    assert_eq!(shifted[1] - shifted[0], 164);
+  }
+
+  #[test]
+  fn coarsen() {
+    let (output, error) =
+      run_test(" convert -p secs --coarsen 1h 1679258022 1679258186 1679261786 -o asc");
+    assert_eq!("3 times in 2 distinct 1h buckets\n", error);
+    assert_eq!("1679256000\n1679256000\n1679259600\n", output);
+  }
+
+  #[test]
+  fn filetime() {
+    let (output, error) = run_test(" convert --epoch filetime filetime:133237316220000000");
+    assert_eq!("", error);
+    assert_eq!("133237316220000000\n", output);
+  }
+
+  #[test]
+  fn unknown_epoch() {
+    let (output, error) = run_test(" convert nope:123");
+    assert_eq!("", output);
+    assert_eq!(
+      "Unknown epoch scheme nope, pass --epoch nope to run epc-epoch-nope\n",
+      error
+    );
+
+    let (output, error) = run_test(" convert --epoch nope 0");
+    assert_eq!("", output);
+    assert_eq!(
+      "Unknown epoch scheme nope, no epc-epoch-nope on the PATH\n",
+      error
+    );
+
+    let (output, error) = run_test(" convert --epoch filetime nope:123");
+    assert_eq!("", output);
+    assert_eq!(
+      "Unknown epoch scheme nope, pass --epoch nope to run epc-epoch-nope\n",
+      error
+    );
+  }
+
+  #[test]
+  fn many_zones() {
+    let (output, error) =
+      run_test(" convert -p secs -f=%R%z -t=UTC -t=NYC -t=Tokyo 1679258022 1679261622");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        20:33+0000
+        16:33-0400
+        05:33+0900
+        21:33+0000
+        17:33-0400
+        06:33+0900
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn fixed_offsets() {
+    let (output, error) =
+      run_test(" convert -p secs -f=%R%z -t=+05:30 -t=UTC-7 -t=gmt+0545 1679258022");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        02:03+0530
+        13:33-0700
+        02:18+0545
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn relative_over_format() {
+    let (output, error) = run_test(" convert -r -f=%F -p secs 0");
+    assert_eq!("", error);
+    assert!(output.ends_with(" years ago\n"), "{}", output);
+  }
+
+  #[test]
+  fn zone_abbreviation() {
+    // IANA's EST stays fixed while New York is on daylight saving
+    let (output, error) = run_test(" convert -p secs -f=%R%z -t=est -t=nyc 1679258022");
+    assert_eq!("", error);
+    assert_eq!("15:33-0500\n16:33-0400\n", output);
+  }
+
+  #[test]
+  fn utc_overrides() {
+    let (output, error) = run_test(" convert -p secs -f=%R%z -t=NYC -U 1679258022");
+    assert_eq!("", error);
+    assert_eq!("20:33+0000\n", output);
+  }
+
+  #[test]
+  fn input_timezone() {
+    let (output, error) =
+      run_test(" convert -f=%R%z -t=Tokyo --input-timezone=NYC 2023-03-19T16:33:42");
+    assert_eq!("", error);
+    assert_eq!("05:33+0900\n", output);
+  }
+
+  #[test]
+  fn own_zone() {
+    let (output, error) = run_test(
+      " convert -f=%FT%T%:z 1679258022000 2025-03-30T03:30[Europe/Berlin] 1679258022000[+05:30]",
+    );
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2023-03-19T20:33:42+00:00
+        2025-03-30T03:30:00+02:00
+        2023-03-20T02:03:42+05:30
+      "},
+      output
+    );
+
+    let (output, _) = run_test(" convert -f=%FT%T%:z -U 2025-03-30T03:30[Europe/Berlin]");
+    assert_eq!("2025-03-30T01:30:00+00:00\n", output);
+  }
+
+  #[test]
+  fn dst_policy() {
+    let (output, error) =
+      run_test(" convert -f=%R%z -t=NYC --dst=latest 2023-03-12T02:30:00 2023-11-05T01:30:00");
+    assert_eq!("", error);
+    assert_eq!("03:30-0400\n01:30-0500\n", output);
+
+    let (_, error) = run_test(" convert -t=NYC --dst=error 2023-11-05T01:30:00");
+    assert_eq!(
+      "2023-11-05T01:30:00 is ambiguous in America/New_York, could be 2023-11-05T01:30:00-04:00 or 2023-11-05T01:30:00-05:00\n",
+      error
+    );
+  }
+
   #[test]
   fn millis() {
     let (output, error) = run_test(" convert 1679661279000 1679661179000 1679661079000");
@@ -238,4 +531,203 @@ mod test {
       output
     );
   }
+
+  #[test]
+  fn fractional() {
+    let (output, error) = run_test(" convert -p secs 1679258022.123456 -f=%s%.9f");
+    assert_eq!("", error);
+    assert_eq!("1679258022.123456000\n", output);
+
+    let (output, error) = run_test(" convert 1679258022123.456789 -f=%s%.9f");
+    assert_eq!("", error);
+    assert_eq!("1679258022.123456789\n", output);
+  }
+
+  #[test]
+  fn before_epoch() {
+    let (output, error) =
+      run_test(" convert -p secs -1679258022 -5 1969-07-20T20:17:40Z -o asc -a -1h -u mins");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        -1679261640
+        -14186580
+        -3660
+      "},
+      output
+    );
+
+    let (output, error) = run_test(" convert -p secs -1679258022.5 -f=%FT%T%.3f");
+    assert_eq!("", error);
+    assert_eq!("1916-10-15T03:26:17.500\n", output);
+  }
+
+  #[test]
+  fn subsec_rounding() {
+    let input =
+      " convert -p secs 2023-03-19T20:33:42.5Z 2023-03-19T20:33:43.5Z 2023-03-19T20:33:43.7Z";
+    let (output, error) = run_test(input);
+    assert_eq!("", error);
+    assert_eq!("1679258022\n1679258023\n1679258023\n", output);
+
+    let (output, error) = run_test(&format!("{} --subsec-rounding half-even", input));
+    assert_eq!("", error);
+    assert_eq!("1679258022\n1679258024\n1679258024\n", output);
+  }
+
+  #[test]
+  fn solar_column() {
+    let (output, error) =
+      run_test(" convert -p secs -t=America/New_York -f=%T --longitude -74.006 1679258022");
+    assert_eq!("", error);
+    assert_eq!("16:33:42\tsolar 15:37:41 (-56m 1s)\n", output);
+  }
+
+  #[test]
+  fn js_safe() {
+    let (code, output, error) =
+      run_test_code(" convert -p nanos --js-safe 1679258022123456789 1679258022");
+    assert_eq!("1679258022123456789\n1679258022\n", output);
+    assert_eq!(
+      "1679258022123456789 exceeds 2^53-1 and is not safe as a JavaScript Number, try -p micros\n",
+      error
+    );
+    assert_eq!(ExitCode::FAILURE, code);
+  }
+
+  #[test]
+  fn nanos_out_of_range() {
+    let (code, output, error) = run_test_code(" convert -p nanos 1600-01-01T00:00:00Z");
+    assert_eq!("", output);
+    assert_eq!(
+      "Could not print 1600-01-01 00:00:00 as nanos: out of range\n",
+      error
+    );
+    assert_eq!(ExitCode::FAILURE, code);
+  }
+
+  #[test]
+  fn inverse() {
+    let (output, error) = run_test(" convert -p secs -a -1h --inverse 1679258022 1679258022.5");
+    assert_eq!("1679254422\n1679254422\n", output);
+    assert_eq!(
+      "undo with: epc convert -p nanos -a=\"1h\" 2023-03-19T19:33:42Z 2023-03-19T19:33:42.500Z\n",
+      error
+    );
+
+    let (output, error) =
+      run_test(" convert -p nanos -a=1h 2023-03-19T19:33:42Z 2023-03-19T19:33:42.500Z");
+    assert_eq!("", error);
+    assert_eq!("1679258022000000000\n1679258022500000000\n", output);
+  }
+
+  #[test]
+  fn inverse_month_end() {
+    let (output, error) =
+      run_test(" convert -p secs --inverse -a 1mo 2023-01-31T00:00:00Z 2023-01-15T00:00:00Z");
+    assert_eq!("1677542400\n1676419200\n", output);
+    assert_eq!(
+      indoc! {"
+        undo with: epc convert -p nanos -a=\"-1mo\" 2023-02-28T00:00:00Z 2023-02-15T00:00:00Z
+        which cannot restore 2023-01-31T00:00:00Z, as months clamp to the end of shorter ones
+      "},
+      error
+    );
+  }
+
+  #[test]
+  fn repeated_add() {
+    let (output, error) = run_test(" convert -p secs -a 1mo -a -3d --inverse 2023-01-31T00:00:00Z");
+    assert_eq!("1677283200\n", output);
+    assert_eq!(
+      indoc! {"
+        undo with: epc convert -p nanos -a=\"3d\" -a=\"-1mo\" 2023-02-25T00:00:00Z
+        which cannot restore 2023-01-31T00:00:00Z, as months clamp to the end of shorter ones
+      "},
+      error
+    );
+
+    let (output, error) = run_test(" convert -p secs -a -3d -a 1mo 2023-01-31T00:00:00Z");
+    assert_eq!("", error);
+    assert_eq!("1677542400\n", output);
+  }
+
+  #[test]
+  fn business_days() {
+    let (output, error) = run_test(
+      " convert -t=UTC -f=%FT%T --add-business-days 2 --holiday 2023-03-20 2023-03-17T15:00:00Z",
+    );
+    assert_eq!("", error);
+    assert_eq!("2023-03-22T15:00:00\n", output);
+  }
+
+  #[test]
+  fn truncate_in_target() {
+    let (output, error) =
+      run_test(" convert -t=Asia/Tokyo -u days -f=%FT%T%z 2023-03-17T20:00:00Z");
+    assert_eq!("", error);
+    assert_eq!("2023-03-18T00:00:00+0900\n", output);
+  }
+
+  #[test]
+  fn next_weekday() {
+    let (output, error) =
+      run_test(" convert -t=Asia/Tokyo -f=%FT%T --next fri --midnight 2023-03-17T15:30:00Z");
+    assert_eq!("", error);
+    assert_eq!("2023-03-24T00:00:00\n", output);
+  }
+
+  #[test]
+  fn sub() {
+    let (output, error) = run_test(" convert -p secs --sub 1h --sub 30m --inverse 1679258022");
+    assert_eq!("1679252622\n", output);
+    assert_eq!(
+      "undo with: epc convert -p nanos -a=\"30m\" -a=\"1h\" 2023-03-19T19:03:42Z\n",
+      error
+    );
+  }
+
+  #[test]
+  fn cloud_presets() {
+    let (output, error) =
+      run_test(" convert 2023-03-19T16:36:26.123456789Z 1679243786123 -t=America/New_York -f=@gcp");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2023-03-19T16:36:26.123456789Z
+        2023-03-19T16:36:26.123000000Z
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn auto_precision() {
+    let (output, error) =
+      run_test(" convert -p auto 1679258022 1679258022123 1679258022123456 1679258022123456789");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        1679258022000
+        1679258022123
+        1679258022123
+        1679258022123
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn gnu_epoch() {
+    let (output, error) = run_test(" convert -p secs @1679258022 @1679258022.999 1679258186");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        1679258022
+        1679258022
+        1679258186
+      "},
+      output
+    );
+  }
 }
@@ -0,0 +1,126 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+  thread,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, DurationArgs, FormatArgs},
+  Handler,
+};
+
+/// Returns to the start of the line and clears it, so each frame overwrites the last
+const REDRAW: &str = "\r\x1b[2K";
+
+#[derive(Args)]
+pub struct CountdownArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// The time to count down to
+  #[arg(allow_negative_numbers = true)]
+  input: ConversionInput,
+}
+
+impl CountdownArgs {
+  /// Renders a remaining duration, which handle has checked will format
+  fn shown(&self, d: Duration) -> String {
+    self.duration.format(d).unwrap_or_else(|e| e)
+  }
+
+  /// Redraws the remaining time each time its whole seconds change, until
+  /// the target is reached
+  fn count<W: Write>(
+    &self,
+    mut out: W,
+    target: DateTime<Utc>,
+    mut now: impl FnMut() -> DateTime<Utc>,
+    mut sleep: impl FnMut(Duration),
+  ) -> Result<(), io::Error> {
+    loop {
+      let remaining = target - now();
+      if remaining <= Duration::zero() {
+        return writeln!(&mut out, "{}{}", REDRAW, self.shown(Duration::zero()));
+      }
+      // Round up, so the display only reads zero once the target is reached
+      let shown = Duration::seconds((remaining.num_milliseconds() + 999) / 1000);
+      write!(&mut out, "{}{}", REDRAW, self.shown(shown))?;
+      out.flush()?;
+      sleep(remaining - (shown - Duration::seconds(1)));
+    }
+  }
+}
+
+impl Handler for CountdownArgs {
+  fn handle<W, E>(&self, out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let target = self
+      .duration
+      .check()
+      .and_then(|_| self.input.to_dt(&self.format.precision, &now));
+    let target = match target {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(dt) => dt.with_timezone(&Utc),
+    };
+    self.count(out, target, Utc::now, |nap| {
+      thread::sleep(nap.to_std().unwrap_or_default())
+    })?;
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::cell::Cell;
+
+  use chrono::{Duration, TimeZone, Utc};
+  use clap::Parser;
+
+  use crate::{run_test, Cli, Commands};
+
+  #[test]
+  fn reached() {
+    let (output, error) = run_test(" countdown -p secs 0");
+    assert_eq!("", error);
+    assert_eq!("\r\x1b[2K0s\n", output);
+  }
+
+  #[test]
+  fn frames() {
+    let Some(Commands::Countdown(args)) = Cli::parse_from(["epc", "countdown", "0"]).commands
+    else {
+      panic!("Not a countdown");
+    };
+    let start = Utc.timestamp_opt(1679258022, 0).unwrap();
+    let clock = Cell::new(start);
+    let mut out = Vec::new();
+    let target = start + Duration::milliseconds(2500);
+    args
+      .count(
+        &mut out,
+        target,
+        || clock.get(),
+        |nap| clock.set(clock.get() + nap),
+      )
+      .unwrap();
+    let frames = String::from_utf8(out).unwrap();
+    assert_eq!(
+      frames.split("\r\x1b[2K").collect::<Vec<_>>(),
+      vec!["", "3s", "2s", "1s", "0s\n"]
+    );
+  }
+}
@@ -0,0 +1,245 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{DateTime, TimeZone, Timelike, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs, Schedule, Zone},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct CronArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// How many upcoming trigger times to print
+  #[arg(long, short = 'n', default_value_t = 5)]
+  iterations: usize,
+
+  /// Find trigger times after this time rather than now
+  #[arg(long, allow_negative_numbers = true, conflicts_with = "covers")]
+  from: Option<ConversionInput>,
+
+  /// Rather than listing trigger times, check whether the schedule fires at
+  /// this time. A zone suffix like [Europe/Berlin] sets the zone the
+  /// schedule runs in, otherwise the -t timezone is used. Exits 0 when it
+  /// fires, 1 when it does not, and 2 on error
+  #[arg(long, allow_negative_numbers = true)]
+  covers: Option<ConversionInput>,
+
+  /// A five field cron expression, such as "0 2 * * SUN"
+  #[arg(required = true)]
+  expression: Vec<String>,
+}
+
+impl Handler for CronArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let schedule = match Schedule::cron(&self.expression.join(" ")) {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(2)),
+      Ok(s) => s,
+    };
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+
+    if let Some(covers) = &self.covers {
+      let zone = covers.zone().unwrap_or(into_tz);
+      return match covers.to_dt(&self.format.precision, &now) {
+        Err(e) => writeln!(&mut err, "{}", e).map(|_| ExitCode::from(2)),
+        Ok(at) => self.explain(&mut out, &mut err, &schedule, &at.with_timezone(&zone)),
+      };
+    }
+
+    let from = match &self.from {
+      None => Ok(now),
+      Some(inp) => inp
+        .to_dt(&self.format.precision, &now)
+        .map(|dt| dt.with_timezone(&into_tz)),
+    };
+    let mut at = match from {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(2)),
+      Ok(dt) => dt,
+    };
+    for i in 0..self.iterations {
+      let Some(next) = schedule.next_after(&at) else {
+        if i == 0 {
+          return writeln!(
+            &mut err,
+            "{} has no upcoming trigger time",
+            self.expression.join(" ")
+          )
+          .map(|_| ExitCode::from(2));
+        }
+        break;
+      };
+      match self.format.format(&next) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(2)),
+        Ok(shown) => writeln!(&mut out, "{}", shown)?,
+      }
+      at = next;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+impl CronArgs {
+  /// Says whether the schedule fires in the same minute as the given time,
+  /// and if not, whether DST is why
+  fn explain<W: Write, E: Write>(
+    &self,
+    mut out: W,
+    mut err: E,
+    schedule: &Schedule,
+    at: &DateTime<Zone>,
+  ) -> Result<ExitCode, io::Error> {
+    let zone = at.timezone();
+    let minute = at
+      .naive_local()
+      .with_second(0)
+      .and_then(|n| n.with_nanosecond(0))
+      .unwrap_or_else(|| at.naive_local());
+    // When the minute repeats, only its first occurrence fires
+    let fired = zone.from_local_datetime(&minute).earliest();
+    let at_minute = *at - (at.naive_local() - minute);
+    let formatted = self.format.format(at).and_then(|shown| {
+      let first = fired
+        .filter(|f| *f != at_minute)
+        .map(|f| self.format.format(&f))
+        .transpose()?;
+      let next = schedule
+        .next_after(at)
+        .map(|n| self.format.format(&n))
+        .transpose()?;
+      Ok((shown, first, next))
+    });
+    let (shown, first, next) = match formatted {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(2)),
+      Ok(v) => v,
+    };
+
+    if schedule.matches(&minute) {
+      if let Some(first) = first {
+        writeln!(
+          &mut out,
+          "no: {} in {} is repeated by DST, and the schedule fired on its first occurrence at {}",
+          shown, zone, first
+        )?;
+        return Ok(ExitCode::from(1));
+      }
+      writeln!(&mut out, "yes: the schedule fires at {} in {}", shown, zone)?;
+      return Ok(ExitCode::SUCCESS);
+    }
+
+    writeln!(
+      &mut out,
+      "no: the schedule does not fire at {} in {}",
+      shown, zone
+    )?;
+    let skipped = schedule.skipped_on(minute.date(), &zone);
+    if !skipped.is_empty() {
+      let times = skipped
+        .iter()
+        .map(|n| n.format("%H:%M").to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+      writeln!(
+        &mut out,
+        "{} on {} did not exist in {}, skipped by DST, so those runs never happened",
+        times,
+        minute.date(),
+        zone
+      )?;
+    }
+    if let Some(next) = next {
+      writeln!(&mut out, "next run: {}", next)?;
+    }
+    Ok(ExitCode::from(1))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::process::ExitCode;
+
+  use crate::{run_test, run_test_code};
+  use indoc::indoc;
+
+  #[test]
+  fn upcoming() {
+    let (output, error) = run_test(" cron -n 2 -f=%a%t%R --from 2023-03-17T18:00:00Z 0 2 * * SUN");
+    assert_eq!("", error);
+    assert_eq!("Sun\t02:00\nSun\t02:00\n", output);
+  }
+
+  #[test]
+  fn covers_dst_gap() {
+    let (code, output, error) =
+      run_test_code(" cron -f=%FT%R%:z --covers 2025-03-30T02:30+01:00[Europe/Berlin] 0 2 * * SUN");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        no: the schedule does not fire at 2025-03-30T03:30+02:00 in Europe/Berlin
+        02:00 on 2025-03-30 did not exist in Europe/Berlin, skipped by DST, so those runs never happened
+        next run: 2025-04-06T02:00+02:00
+      "},
+      output
+    );
+    assert_eq!(ExitCode::from(1), code);
+  }
+
+  #[test]
+  fn covers_match() {
+    let (code, output, error) = run_test_code(
+      " cron -f=%FT%T%:z --covers 2025-03-23T02:00:59+01:00[Europe/Berlin] 0 2 * * SUN",
+    );
+    assert_eq!("", error);
+    assert_eq!(
+      "yes: the schedule fires at 2025-03-23T02:00:59+01:00 in Europe/Berlin\n",
+      output
+    );
+    assert_eq!(ExitCode::SUCCESS, code);
+  }
+
+  #[test]
+  fn covers_repeated() {
+    // 02:30 happens twice as clocks fall back, once at +02:00 then at +01:00
+    let (code, output, error) =
+      run_test_code(" cron -f=%FT%R%:z --covers 2025-10-26T02:30+01:00[Europe/Berlin] 30 2 * * *");
+    assert_eq!("", error);
+    assert_eq!(
+      "no: 2025-10-26T02:30+01:00 in Europe/Berlin is repeated by DST, and the schedule fired on its first occurrence at 2025-10-26T02:30+02:00\n",
+      output
+    );
+    assert_eq!(ExitCode::from(1), code);
+  }
+
+  #[test]
+  fn invalid() {
+    let (code, _, error) = run_test_code(" cron 0 2 * *");
+    assert_eq!("Expected 5 cron fields but got: 0 2 * *\n", error);
+    assert_eq!(ExitCode::from(2), code);
+
+    let (code, output, error) = run_test_code(" cron */0 * * * *");
+    assert_eq!("", output);
+    assert_eq!("Invalid value */0 for range 0-59\n", error);
+    assert_eq!(ExitCode::from(2), code);
+  }
+
+  #[test]
+  fn never_fires() {
+    let (code, output, error) = run_test_code(" cron 0 0 31 2 *");
+    assert_eq!("", output);
+    assert_eq!("0 0 31 2 * has no upcoming trigger time\n", error);
+    assert_eq!(ExitCode::from(2), code);
+  }
+}
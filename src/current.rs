@@ -1,9 +1,12 @@
 use chrono::Utc;
 use clap::Args;
-use std::io::{self, Write};
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
 
 use crate::{
-  common::{AtTimezoneArgs, CalcArgs, FormatArgs, TruncateArgs},
+  common::{AtTimezoneArgs, CalcArgs, FormatArgs, SnapArgs, TruncateArgs, WeekStartArgs},
   Handler,
 };
 
@@ -15,28 +18,43 @@ pub struct CurrentArgs {
   #[command(flatten)]
   format: FormatArgs,
 
+  #[command(flatten)]
+  snap: SnapArgs,
+
   #[command(flatten)]
   add: CalcArgs,
 
   #[command(flatten)]
   truncate: TruncateArgs,
+
+  #[command(flatten)]
+  week: WeekStartArgs,
 }
 
 impl Handler for CurrentArgs {
-  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<(), io::Error>
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
   where
     W: Write,
     E: Write,
   {
     let rdt = self
       .truncate
-      .apply(Utc::now().into())
-      .map(|dt| dt.with_timezone(&self.timezone.get()))
+      .apply(
+        Utc::now().with_timezone(&self.timezone.get()),
+        self.week.get(),
+      )
+      .and_then(|dt| self.snap.apply(dt, self.week.get()))
       .and_then(|dt| self.add.eval(dt));
     let dt = match rdt {
-      Err(e) => return write!(&mut err, "{}", e),
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
       Ok(v) => v,
     };
-    writeln!(&mut out, "{}", self.format.format(&dt))
+    for tz in self.timezone.all() {
+      match self.format.format(&dt.with_timezone(&tz)) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => writeln!(&mut out, "{}", shown)?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
   }
 }
@@ -0,0 +1,480 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{
+    business_time, AtTimezoneArgs, BusinessHours, ConversionInput, DurationArgs, FormatArgs,
+    HolidayArgs, Holidays, Zone,
+  },
+  hduration::HDuration,
+  Handler,
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum OutputStructure {
+  /// One delta per line
+  List,
+  /// Aligned columns of the two times alongside their delta
+  Table,
+  /// Every input against every other, with row i column j holding the
+  /// delta from input i to input j
+  Matrix,
+  /// Comma separated rows of a,b,delta for every pair of inputs, quoting any
+  /// field that holds a comma
+  Pairs,
+  /// A JSON array of {a, b, delta_ms, delta_human} objects
+  Json,
+}
+
+#[derive(Args)]
+pub struct DeltaArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// How to lay out the computed deltas
+  #[arg(value_enum, long, short = 's', default_value_t = OutputStructure::List)]
+  structure: OutputStructure,
+
+  /// Diff every input against this one reference time instead of the input
+  /// before it
+  #[arg(long, allow_negative_numbers = true)]
+  against: Option<ConversionInput>,
+
+  /// Only count weekday time, optionally just inside daily hours such as
+  /// 09:00-17:00, evaluated in the target timezone
+  #[arg(
+    long,
+    short = 'b',
+    num_args = 0..=1,
+    require_equals = true,
+    default_missing_value = "00:00-24:00"
+  )]
+  business: Option<BusinessHours>,
+
+  #[command(flatten)]
+  holidays: HolidayArgs,
+
+  /// Diff every input against now, as a single input is
+  #[arg(long, conflicts_with = "against")]
+  from_now: bool,
+
+  /// Times to diff, each against the one before it, or against every other
+  /// for the matrix and pairs structures. A single time is diffed against now
+  #[arg(required = true, allow_negative_numbers = true)]
+  input: Vec<ConversionInput>,
+}
+
+impl Handler for DeltaArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let resolve = |inp: &ConversionInput| {
+      inp
+        .to_dt(&self.format.precision, &now)
+        .map(|dt| dt.with_timezone(&into_tz))
+    };
+    let holidays = || match self.business {
+      Some(_) => self.holidays.load(),
+      None => Ok(Holidays::default()),
+    };
+    let maybe_datetimes = self
+      .input
+      .iter()
+      .map(resolve)
+      .collect::<Result<Vec<_>, _>>()
+      .and_then(|dts| {
+        let against = self.against.as_ref().map(resolve).transpose()?;
+        Ok((dts, against, holidays()?))
+      });
+    let (mut dts, against, holidays) = match maybe_datetimes {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+    if (against.is_some() || self.from_now)
+      && matches!(
+        self.structure,
+        OutputStructure::Matrix | OutputStructure::Pairs
+      )
+    {
+      return writeln!(
+        &mut err,
+        "--against and --from-now only apply to the list and table structures"
+      )
+      .map(|_| ExitCode::FAILURE);
+    }
+    if dts.len() == 1 && against.is_none() && !self.from_now {
+      dts.push(now);
+    }
+
+    let pairs = || match against {
+      Some(r) => dts.iter().map(|b| (r, *b)).collect::<Vec<_>>(),
+      None if self.from_now => dts.iter().map(|a| (*a, now)).collect(),
+      None => dts.windows(2).map(|w| (w[0], w[1])).collect(),
+    };
+    let windows = || {
+      pairs()
+        .iter()
+        .map(|(a, b)| {
+          Ok((
+            self.format.format(a)?,
+            self.format.format(b)?,
+            self.duration.format(self.diff(a, b, &holidays))?,
+          ))
+        })
+        .collect::<Result<Vec<_>, String>>()
+    };
+
+    let lines = match self.structure {
+      OutputStructure::List => {
+        windows().map(|rows| rows.into_iter().map(|(_, _, delta)| delta).collect())
+      }
+      OutputStructure::Table => windows().map(|rows| {
+        let a_width = rows.iter().map(|(a, _, _)| a.len()).max().unwrap_or(0);
+        let b_width = rows.iter().map(|(_, b, _)| b.len()).max().unwrap_or(0);
+        rows
+          .iter()
+          .map(|(a, b, delta)| format!("{:<a_width$}  {:<b_width$}  {}", a, b, delta))
+          .collect()
+      }),
+      OutputStructure::Matrix => self.matrix(&dts, &holidays),
+      OutputStructure::Pairs => self.pairs(&dts, &holidays),
+      OutputStructure::Json => self.json(&pairs(), &holidays),
+    };
+    match lines {
+      Err(e) => writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(lines) => lines
+        .iter()
+        .try_for_each(|line| writeln!(&mut out, "{}", line))
+        .map(|_| ExitCode::SUCCESS),
+    }
+  }
+}
+
+/// Quotes a string for JSON, escaping anything a custom format could contain
+fn json_string(s: &str) -> String {
+  let mut quoted = String::from('"');
+  for c in s.chars() {
+    match c {
+      '"' => quoted.push_str("\\\""),
+      '\\' => quoted.push_str("\\\\"),
+      c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+      c => quoted.push(c),
+    }
+  }
+  quoted.push('"');
+  quoted
+}
+
+/// Quotes a CSV field when it holds a comma, quote, or line break, doubling
+/// any quotes inside it
+fn csv_field(s: &str) -> String {
+  match s.contains([',', '"', '\n', '\r']) {
+    true => format!("\"{}\"", s.replace('"', "\"\"")),
+    false => s.to_string(),
+  }
+}
+
+impl DeltaArgs {
+  /// The time from a to b, counting only business time when asked to
+  fn diff(&self, a: &DateTime<Zone>, b: &DateTime<Zone>, holidays: &Holidays) -> Duration {
+    match &self.business {
+      Some(hours) => business_time(a, b, hours, holidays),
+      None => *b - *a,
+    }
+  }
+
+  fn json(
+    &self,
+    pairs: &[(DateTime<Zone>, DateTime<Zone>)],
+    holidays: &Holidays,
+  ) -> Result<Vec<String>, String> {
+    let objects = pairs
+      .iter()
+      .map(|(a, b)| {
+        Ok(format!(
+          "  {{\"a\": {}, \"b\": {}, \"delta_ms\": {}, \"delta_human\": {}}}",
+          json_string(&self.format.format(a)?),
+          json_string(&self.format.format(b)?),
+          self.diff(a, b, holidays).num_milliseconds(),
+          json_string(&HDuration::from(self.diff(a, b, holidays)).to_string())
+        ))
+      })
+      .collect::<Result<Vec<_>, String>>()?;
+    if objects.is_empty() {
+      return Ok(vec!["[]".into()]);
+    }
+    Ok(vec![format!("[\n{}\n]", objects.join(",\n"))])
+  }
+
+  fn matrix(&self, dts: &[DateTime<Zone>], holidays: &Holidays) -> Result<Vec<String>, String> {
+    let labels = dts
+      .iter()
+      .enumerate()
+      .map(|(i, dt)| Ok(format!("#{} {}", i + 1, self.format.format(dt)?)))
+      .collect::<Result<Vec<_>, String>>()?;
+    let cells = dts
+      .iter()
+      .map(|a| {
+        dts
+          .iter()
+          .map(|b| self.duration.format(self.diff(a, b, holidays)))
+          .collect::<Result<Vec<_>, _>>()
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+    let widths = (0..dts.len())
+      .map(|j| {
+        cells
+          .iter()
+          .map(|row| row[j].len())
+          .chain([format!("#{}", j + 1).len()])
+          .max()
+          .unwrap_or(0)
+      })
+      .collect::<Vec<_>>();
+
+    let header = widths
+      .iter()
+      .enumerate()
+      .map(|(j, w)| format!("{:>w$}", format!("#{}", j + 1)))
+      .collect::<Vec<_>>()
+      .join("  ");
+    let mut lines = vec![format!("{:label_width$}  {}", "", header)];
+    for (label, row) in labels.iter().zip(cells) {
+      let row = row
+        .iter()
+        .zip(&widths)
+        .map(|(cell, w)| format!("{:>w$}", cell))
+        .collect::<Vec<_>>()
+        .join("  ");
+      lines.push(format!("{:<label_width$}  {}", label, row));
+    }
+    Ok(lines)
+  }
+
+  fn pairs(&self, dts: &[DateTime<Zone>], holidays: &Holidays) -> Result<Vec<String>, String> {
+    let mut lines = vec!["a,b,delta".to_string()];
+    for (i, a) in dts.iter().enumerate() {
+      for b in &dts[i + 1..] {
+        lines.push(format!(
+          "{},{},{}",
+          csv_field(&self.format.format(a)?),
+          csv_field(&self.format.format(b)?),
+          csv_field(&self.duration.format(self.diff(a, b, holidays))?)
+        ));
+      }
+    }
+    Ok(lines)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn windows() {
+    let (output, error) = run_test(" delta -p secs 1679258022 1679258186 1679261786");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2m 44s
+        1h
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn negative_with_precision() {
+    let (output, error) = run_test(" delta -p secs -d mins 1679261786 1679258186");
+    assert_eq!("", error);
+    assert_eq!("-60\n", output);
+  }
+
+  #[test]
+  fn unit_range() {
+    let (output, error) = run_test(" delta -p secs --largest hours --smallest mins 0 180065");
+    assert_eq!("", error);
+    assert_eq!("50h 1m\n", output);
+
+    let (output, error) = run_test(" delta -p secs --largest secs --smallest hours 0 180065");
+    assert_eq!("", output);
+    assert_eq!(
+      "The largest unit, secs, is finer than the smallest, hours\n",
+      error
+    );
+  }
+
+  #[test]
+  fn business() {
+    let (output, error) = run_test(" delta -t=UTC -b 2023-03-17T15:00:00Z 2023-03-20T10:00:00Z");
+    assert_eq!("", error);
+    assert_eq!("19h\n", output);
+
+    let (output, error) =
+      run_test(" delta -t=UTC -b=09:00-17:00 2023-03-17T15:00:00Z 2023-03-20T10:00:00Z");
+    assert_eq!("", error);
+    assert_eq!("3h\n", output);
+
+    // Starting weeks on Sunday leaves the weekend on Saturday and Sunday
+    let (output, error) = run_test(
+      " delta -t=UTC -b=09:00-17:00 --week-start sun 2023-03-17T15:00:00Z 2023-03-20T10:00:00Z",
+    );
+    assert_eq!("", error);
+    assert_eq!("3h\n", output);
+  }
+
+  #[test]
+  fn business_holiday() {
+    let (output, error) =
+      run_test(" delta -t=UTC -b --holiday 2023-03-20 2023-03-17T15:00:00Z 2023-03-21T10:00:00Z");
+    assert_eq!("", error);
+    assert_eq!("19h\n", output);
+  }
+
+  #[test]
+  fn decimal() {
+    let (output, error) = run_test(" delta -p secs -d hours --decimal 2 0 5400 -1800");
+    assert_eq!("", error);
+    assert_eq!("1.50\n-2.00\n", output);
+  }
+
+  #[test]
+  fn table() {
+    let (output, error) =
+      run_test(" delta -s table -t=America/New_York -f 2023-03-19T16:33:42-04:00 1679258186000");
+    assert_eq!("", error);
+    assert_eq!(
+      "2023-03-19T16:33:42-0400  2023-03-19T16:36:26-0400  2m 44s\n",
+      output
+    );
+  }
+
+  #[test]
+  fn matrix() {
+    let (output, error) = run_test(" delta -p secs -s matrix 1679258022 1679258186 1679261786");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+                               #1      #2         #3
+        #1 1679258022          0s  2m 44s  1h 2m 44s
+        #2 1679258186     -2m 44s      0s         1h
+        #3 1679261786  -1h 2m 44s     -1h         0s
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn pairs() {
+    let (output, error) = run_test(" delta -p secs -d secs -s pairs 100 160 400");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        a,b,delta
+        100,160,60
+        100,400,300
+        160,400,240
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn pairs_quoted() {
+    let (output, error) = run_test(" delta -f=@http -d secs -s pairs 1679258022000 1679258082000");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {r#"
+        a,b,delta
+        "Sun, 19 Mar 2023 20:33:42 GMT","Sun, 19 Mar 2023 20:34:42 GMT",60
+      "#},
+      output
+    );
+  }
+
+  #[rstest]
+  #[case("plain", "plain")]
+  #[case("a,b", r#""a,b""#)]
+  #[case(r#"say "hi""#, r#""say ""hi""""#)]
+  fn csv_field(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(super::csv_field(input), expected)
+  }
+
+  #[test]
+  fn against() {
+    let (output, error) =
+      run_test(" delta -p secs --against 2023-03-19T20:33:42Z 1679258022 1679258186 1679254422");
+    assert_eq!("", error);
+    assert_eq!("0s\n2m 44s\n-1h\n", output);
+  }
+
+  #[test]
+  fn against_matrix() {
+    let (output, error) = run_test(" delta -p secs -s matrix --against 0 1 2");
+    assert_eq!("", output);
+    assert_eq!(
+      "--against and --from-now only apply to the list and table structures\n",
+      error
+    );
+  }
+
+  #[test]
+  fn from_now() {
+    let (output, error) = run_test(" delta -d days --from-now now 0 yesterday");
+    assert_eq!("", error);
+    let days = output.lines().collect::<Vec<_>>();
+    assert_eq!(3, days.len());
+    assert_eq!(("0", "1"), (days[0], days[2]));
+    assert!(days[1].parse::<i64>().unwrap() > 19000);
+  }
+
+  #[test]
+  fn json() {
+    let (output, error) =
+      run_test(" delta -s json -p secs -f=%H:%M:%S\"%Z 1679258022 1679258186 1679258185.5");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {r#"
+        [
+          {"a": "20:33:42\"UTC", "b": "20:36:26\"UTC", "delta_ms": 164000, "delta_human": "2m 44s"},
+          {"a": "20:36:26\"UTC", "b": "20:36:25\"UTC", "delta_ms": -500, "delta_human": "-500ms"}
+        ]
+      "#},
+      output
+    );
+  }
+
+  #[rstest]
+  #[case("plain", r#""plain""#)]
+  #[case("a\\b\n", r#""a\\b\u000a""#)]
+  fn json_string(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(super::json_string(input), expected)
+  }
+
+  #[test]
+  fn against_now() {
+    let (output, error) = run_test(" delta now now");
+    assert_eq!("", error);
+    assert_eq!("0s\n", output);
+  }
+}
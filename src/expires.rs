@@ -0,0 +1,140 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::Utc;
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, DurationArgs, FormatArgs},
+  hduration::HDuration,
+  Handler,
+};
+
+#[derive(Args)]
+pub struct ExpiresArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  // These are parsed in handle, as clap's usage errors exit 2 and would
+  // read as CRIT rather than unresolved
+  /// Warn when the expiry is closer than this duration
+  #[arg(long, short = 'w', default_value = "14d")]
+  warn: String,
+
+  /// Go critical when the expiry is closer than this duration
+  #[arg(long, short = 'c', default_value = "3d")]
+  crit: String,
+
+  /// The time something expires at
+  #[arg(allow_negative_numbers = true)]
+  input: String,
+}
+
+impl Handler for ExpiresArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let threshold = |s: &str| {
+      s.parse::<HDuration>()
+        .and_then(|d| chrono::Duration::from_std(d.inner).map_err(|e| format!("{}", e)))
+    };
+    let thresholds = threshold(&self.warn).and_then(|w| Ok((w, threshold(&self.crit)?)));
+    let resolved = thresholds.and_then(|t| {
+      self
+        .input
+        .parse::<ConversionInput>()
+        .and_then(|inp| inp.to_dt(&self.format.precision, &now))
+        .map(|dt| (t, dt.with_timezone(&into_tz)))
+    });
+    let ((warn, crit), dt) = match resolved {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(3)),
+      Ok(v) => v,
+    };
+
+    let shown = match self.format.format(&dt) {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(3)),
+      Ok(shown) => shown,
+    };
+    let remaining = dt - now;
+    let (status, code) = if remaining <= crit {
+      ("CRIT", 2)
+    } else if remaining <= warn {
+      ("WARN", 1)
+    } else {
+      ("OK", 0)
+    };
+    let when = if remaining < chrono::Duration::zero() {
+      self
+        .duration
+        .format(-remaining)
+        .map(|d| format!("expired {} ago", d))
+    } else {
+      self
+        .duration
+        .format(remaining)
+        .map(|d| format!("expires in {}", d))
+    };
+    let when = match when {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::from(3)),
+      Ok(when) => when,
+    };
+    writeln!(&mut out, "{}: {} {}", status, shown, when)?;
+    Ok(ExitCode::from(code))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::process::ExitCode;
+
+  use crate::run_test_code;
+
+  #[test]
+  fn ok() {
+    let (code, output, error) = run_test_code(" expires -p secs 4102444800");
+    assert_eq!("", error);
+    assert!(output.starts_with("OK: 4102444800 expires in "));
+    assert_eq!(ExitCode::from(0), code);
+  }
+
+  #[test]
+  fn warn() {
+    let (code, output, error) = run_test_code(" expires -p secs -w 100000w 4102444800");
+    assert_eq!("", error);
+    assert!(output.starts_with("WARN: 4102444800 expires in "));
+    assert_eq!(ExitCode::from(1), code);
+  }
+
+  #[test]
+  fn expired() {
+    let (code, output, error) = run_test_code(" expires -p secs 1679258022");
+    assert_eq!("", error);
+    assert!(output.starts_with("CRIT: 1679258022 expired "));
+    assert!(output.ends_with(" ago\n"));
+    assert_eq!(ExitCode::from(2), code);
+  }
+
+  #[test]
+  fn unresolvable() {
+    let (code, output, error) = run_test_code(" expires garbage");
+    assert_eq!("", output);
+    assert!(!error.is_empty());
+    assert_eq!(ExitCode::from(3), code);
+
+    let (code, _, error) = run_test_code(" expires -p secs -w soon 4102444800");
+    assert!(!error.is_empty());
+    assert_eq!(ExitCode::from(3), code);
+  }
+}
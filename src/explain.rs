@@ -0,0 +1,121 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::SecondsFormat;
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{Precision, JS_MAX_SAFE},
+  Handler,
+};
+
+/// Integer containers stamps commonly get squeezed into, with their largest value
+const CONTAINERS: [(&str, i64); 3] = [
+  ("i32", i32::MAX as i64),
+  ("js", JS_MAX_SAFE),
+  ("i64", i64::MAX),
+];
+
+#[derive(Args)]
+pub struct ExplainBitsArgs {
+  /// The stamp to explain
+  #[arg(allow_negative_numbers = true)]
+  stamp: i64,
+}
+
+/// The date a stamp at the given precision reads as, if representable
+fn date(pre: Precision, ts: i64) -> Option<String> {
+  pre
+    .parse(ts)
+    .single()
+    .map(|dt| dt.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+impl Handler for ExplainBitsArgs {
+  fn handle<W, E>(&self, mut out: W, _err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let ts = self.stamp;
+    let bits = 64 - ts.unsigned_abs().leading_zeros();
+    writeln!(&mut out, "{}", ts)?;
+    writeln!(
+      &mut out,
+      "  width: {} bits, {} with a sign bit",
+      bits,
+      bits + 1
+    )?;
+    for (name, max) in CONTAINERS {
+      let fits = ts.unsigned_abs() <= max as u64;
+      writeln!(
+        &mut out,
+        "  {:<5}  {} (max {})",
+        format!("{}:", name),
+        if fits { "fits" } else { "overflows" },
+        max
+      )?;
+    }
+
+    let likely = Precision::infer(ts);
+    let names = Precision::STAMPED.map(|p| {
+      p.to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default()
+    });
+    let mut header = vec!["precision".to_string(), "reads as".into()];
+    header.extend(CONTAINERS.map(|(name, _)| format!("{} overflows after", name)));
+    let mut rows = vec![header];
+    for (pre, name) in Precision::STAMPED.into_iter().zip(names) {
+      let mut row = vec![
+        format!("{} {}", if pre == likely { '*' } else { ' ' }, name),
+        date(pre, ts).unwrap_or_else(|| "out of range".into()),
+      ];
+      // Containers too large to overflow within representable dates never will
+      row.extend(CONTAINERS.map(|(_, max)| date(pre, max).unwrap_or_else(|| "never".into())));
+      rows.push(row);
+    }
+    let widths = (0..rows[0].len())
+      .map(|i| rows.iter().map(|r| r[i].len()).max().unwrap_or(0))
+      .collect::<Vec<_>>();
+    for row in rows {
+      let line = row
+        .iter()
+        .zip(&widths)
+        .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+        .collect::<Vec<_>>()
+        .join("  ");
+      writeln!(&mut out, "  {}", line.trim_end())?;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn millis() {
+    let (output, error) = run_test(" explain-bits 1679258022000");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        1679258022000
+          width: 41 bits, 42 with a sign bit
+          i32:   overflows (max 2147483647)
+          js:    fits (max 9007199254740991)
+          i64:   fits (max 9223372036854775807)
+          precision  reads as                     i32 overflows after             js overflows after              i64 overflows after
+            secs     +55183-07-27T17:40:00Z       2038-01-19T03:14:07Z            never                           never
+          * millis   2023-03-19T20:33:42Z         1970-01-25T20:31:23.647Z        never                           never
+            micros   1970-01-20T10:27:38.022Z     1970-01-01T00:35:47.483647Z     2255-06-05T23:47:34.740991Z     never
+            nanos    1970-01-01T00:27:59.258022Z  1970-01-01T00:00:02.147483647Z  1970-04-15T05:59:59.254740991Z  2262-04-11T23:47:16.854775807Z
+      "},
+      output
+    );
+  }
+}
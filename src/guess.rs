@@ -0,0 +1,96 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::SecondsFormat;
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{AtTimezoneArgs, Precision},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct GuessArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  /// Stamps to show each interpretation of
+  #[arg(required = true, allow_negative_numbers = true)]
+  stamps: Vec<i64>,
+}
+
+impl Handler for GuessArgs {
+  fn handle<W, E>(&self, mut out: W, _err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    for ts in &self.stamps {
+      let likely = Precision::infer(*ts);
+      writeln!(&mut out, "{}", ts)?;
+      for pre in Precision::STAMPED {
+        let date = pre
+          .parse(*ts)
+          .single()
+          .map(|dt| {
+            dt.with_timezone(&into_tz)
+              .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+          })
+          .unwrap_or_else(|| "out of range".into());
+        let name = pre
+          .to_possible_value()
+          .map(|v| v.get_name().to_string())
+          .unwrap_or_default();
+        writeln!(
+          &mut out,
+          "  {} {:<6} {}",
+          if pre == likely { '*' } else { ' ' },
+          name,
+          date
+        )?;
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn interpretations() {
+    let (output, error) = run_test(" guess 1679258022123");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        1679258022123
+            secs   +55183-07-27T17:42:03Z
+          * millis 2023-03-19T20:33:42.123Z
+            micros 1970-01-20T10:27:38.022123Z
+            nanos  1970-01-01T00:27:59.258022123Z
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn negative() {
+    let (output, error) = run_test(" guess -t=America/New_York -1");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        -1
+          * secs   1969-12-31T18:59:59-05:00
+            millis 1969-12-31T18:59:59.999-05:00
+            micros 1969-12-31T18:59:59.999999-05:00
+            nanos  1969-12-31T18:59:59.999999999-05:00
+      "},
+      output
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use std::{str::FromStr, time::Duration};
+use std::{fmt::Display, str::FromStr, time::Duration};
 
 use crate::common::Precision;
 
@@ -15,17 +15,101 @@ impl HDuration {
       negative,
     }
   }
+
+  /// The signed chrono equivalent, failing if it is too large to represent
+  pub fn to_chrono(&self) -> Result<chrono::Duration, String> {
+    chrono::Duration::from_std(self.inner)
+      .map(|d| if self.negative { -d } else { d })
+      .map_err(|e| format!("{}", e))
+  }
+
+  /// Renders the duration using only units from largest to smallest. Anything
+  /// above largest is counted in it, so 50 hours with days as the largest unit
+  /// is 2d 2h, and any remainder below smallest is dropped. Fails when
+  /// largest is a finer unit than smallest
+  pub fn between(&self, largest: Precision, smallest: Precision) -> Result<String, String> {
+    if largest > smallest {
+      let name =
+        |p: Precision| clap::ValueEnum::to_possible_value(&p).map(|v| v.get_name().to_string());
+      return Err(format!(
+        "The largest unit, {}, is finer than the smallest, {}",
+        name(largest).unwrap_or_default(),
+        name(smallest).unwrap_or_default()
+      ));
+    }
+    let mut nanos = self.inner.as_nanos();
+    let mut parts = Vec::new();
+    for p in [
+      Precision::Weeks,
+      Precision::Days,
+      Precision::Hours,
+      Precision::Mins,
+      Precision::Secs,
+      Precision::Millis,
+      Precision::Micros,
+      Precision::Nanos,
+    ] {
+      if p < largest || p > smallest {
+        continue;
+      }
+      let per = p.nanos_per() as u128;
+      if nanos >= per {
+        parts.push(format!("{}{}", nanos / per, p.unit()));
+        nanos %= per;
+      }
+    }
+    if parts.is_empty() {
+      // Nothing is left to be negative once truncated away
+      let unit = Precision::Secs.clamp(largest, smallest);
+      return Ok(format!("0{}", unit.unit()));
+    }
+    let sign = if self.negative { "-" } else { "" };
+    Ok(format!("{}{}", sign, parts.join(" ")))
+  }
+}
+
+impl From<chrono::Duration> for HDuration {
+  fn from(d: chrono::Duration) -> Self {
+    let negative = d < chrono::Duration::zero();
+    // Negating the minimum duration overflows, so clamp to the largest one instead
+    let abs = if negative {
+      -d.max(-chrono::Duration::milliseconds(i64::MAX))
+    } else {
+      d
+    };
+    HDuration {
+      inner: abs.to_std().unwrap_or_default(),
+      negative,
+    }
+  }
+}
+
+impl Display for HDuration {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let shown = self
+      .between(Precision::Weeks, Precision::Nanos)
+      .map_err(|_| std::fmt::Error)?;
+    write!(f, "{}", shown)
+  }
 }
 
 impl FromStr for HDuration {
   type Err = String;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+      Some(rest) => (true, rest),
+      None => (false, s),
+    };
+    if let Some(iso) = unsigned.strip_prefix(['P', 'p']) {
+      return parse_iso(iso, negative).map_err(|e| format!("{}: {}", s, e));
+    }
     let mut sec: u64 = 0;
     let mut nano: u32 = 0;
     let mut is_neg = false;
 
     let mut dbuf: u64 = 0;
+    let mut fbuf: Option<String> = None;
     let mut in_char = false;
     let mut cbuf = String::new();
 
@@ -38,7 +122,7 @@ impl FromStr for HDuration {
       match (should_flush, c) {
         (_, ' ') | (true, _) => {
           in_char = false;
-          let (ds, dn) = flush(dbuf, &cbuf)?;
+          let (ds, dn) = flush(dbuf, fbuf.as_deref().unwrap_or(""), &cbuf)?;
           sec = sec
             .checked_add(ds)
             .ok_or::<String>("Too large of duration".into())?;
@@ -46,14 +130,24 @@ impl FromStr for HDuration {
             .checked_add(dn)
             .ok_or::<String>("Too large of duration".into())?;
           dbuf = c.to_digit(10).unwrap_or(0) as u64;
+          fbuf = None;
           cbuf = String::new();
           if chars.peek().is_none() {
             break;
           }
         }
         (_, '-') if *idx == 0 => is_neg = true,
-        (_, c) if c.is_ascii_digit() => dbuf = dbuf * 10 + c.to_digit(10).unwrap() as u64,
-        (_, 'm' | 's' | 'n' | 'd' | 'h' | 'w') => {
+        (_, '.') if !in_char && fbuf.is_none() => fbuf = Some(String::new()),
+        (_, c) if c.is_ascii_digit() => match fbuf.as_mut() {
+          Some(frac) => frac.push(*c),
+          None => {
+            dbuf = dbuf
+              .checked_mul(10)
+              .and_then(|d| d.checked_add(c.to_digit(10).unwrap() as u64))
+              .ok_or::<String>("Too large of duration".into())?
+          }
+        },
+        (_, 'm' | 's' | 'n' | 'u' | 'µ' | 'μ' | 'd' | 'h' | 'w') => {
           cbuf.push(*c);
           in_char = true;
         }
@@ -65,36 +159,71 @@ impl FromStr for HDuration {
   }
 }
 
-fn flush(dbuf: u64, cbuf: &str) -> Result<(u64, u32), String> {
-  let mut sec: u64 = 0;
-  let mut nano: u32 = 0;
-  let p = Precision::from_str(cbuf)?;
-  match p {
-    Precision::Millis => {
-      sec += dbuf / 1000;
-      let up = (dbuf % 1000)
-        .try_into()
-        .ok()
-        .and_then(|d| nano.checked_add(d));
-      match up {
-        Some(v) => nano += v,
-        None => return Err("Too many milliseconds provided".into()),
-      }
-    }
-    Precision::Nanos => {
-      sec += dbuf / 1000000000;
-      let up = (dbuf % 1000000000)
-        .try_into()
-        .ok()
-        .and_then(|d| nano.checked_add(d));
-      match up {
-        Some(v) => nano += v,
-        None => return Err("Too many nanoseconds provided".into()),
+/// Parses the part of an ISO 8601 duration after its P, such as 3DT4H30M or
+/// T1.5S. Years and months are refused, having no fixed length
+fn parse_iso(s: &str, negative: bool) -> Result<HDuration, String> {
+  let (date, time) = match s.split_once(['T', 't']) {
+    Some((_, "")) => return Err("expected a time after T".into()),
+    Some((date, time)) => (date, Some(time)),
+    None => (s, None),
+  };
+  if date.is_empty() && time.is_none() {
+    return Err("expected at least one component".into());
+  }
+  let mut nanos: u128 = 0;
+  for (part, in_time) in [(date, false), (time.unwrap_or(""), true)] {
+    let mut rest = part;
+    while !rest.is_empty() {
+      let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .ok_or_else(|| format!("{} has no unit", rest))?;
+      let (number, unit) = (&rest[..end], rest[end..].chars().next().unwrap_or(' '));
+      let secs_per: u128 = match (unit.to_ascii_uppercase(), in_time) {
+        ('W', false) => 604_800,
+        ('D', false) => 86_400,
+        ('H', true) => 3_600,
+        ('M', true) => 60,
+        ('S', true) => 1,
+        ('Y' | 'M', false) => return Err("years and months have no fixed length".into()),
+        (c, _) => return Err(format!("unexpected {}", c)),
+      };
+      let (whole, frac) = number.split_once(['.', ',']).unwrap_or((number, ""));
+      if whole.is_empty() || frac.len() > 9 {
+        return Err(format!("{} is not a supported number", number));
       }
+      let whole = whole.parse::<u128>().map_err(|e| e.to_string())?;
+      let frac = format!("{:0<9}", frac)
+        .parse::<u128>()
+        .map_err(|e| e.to_string())?;
+      nanos = whole
+        .checked_mul(secs_per * 1_000_000_000)
+        .and_then(|n| n.checked_add(frac * secs_per))
+        .and_then(|n| nanos.checked_add(n))
+        .ok_or("Too large of duration")?;
+      rest = &rest[end + unit.len_utf8()..];
     }
-    _ => sec += dbuf * p.seconds_per() as u64,
   }
-  Ok((sec, nano))
+  let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| "Too large of duration")?;
+  Ok(HDuration::new(
+    secs,
+    (nanos % 1_000_000_000) as u32,
+    negative,
+  ))
+}
+
+/// Converts a count of the unit into seconds and nanoseconds, carrying any
+/// fraction of it down into the smaller units
+fn flush(dbuf: u64, fbuf: &str, cbuf: &str) -> Result<(u64, u32), String> {
+  let p = Precision::from_str(cbuf)?;
+  if fbuf.len() > 9 {
+    return Err(format!("Too many decimal places in {}.{}", dbuf, fbuf));
+  }
+  let per = p.nanos_per() as u128;
+  let frac = format!("{:0<9}", fbuf).parse::<u128>().unwrap_or(0) * per / 1_000_000_000;
+  let nanos = dbuf as u128 * per + frac;
+  let sec =
+    u64::try_from(nanos / 1_000_000_000).map_err(|_| format!("Too many {} provided", p.unit()))?;
+  Ok((sec, (nanos % 1_000_000_000) as u32))
 }
 
 #[cfg(test)]
@@ -104,6 +233,7 @@ mod test {
   use rstest::*;
 
   use super::HDuration;
+  use crate::common::Precision;
 
   #[test]
   fn from_strt() {
@@ -120,22 +250,129 @@ mod test {
   #[case("10ns 1s", HDuration::new(1, 10, false))]
   #[case("-1ns", HDuration::new(0, 1, true))]
   #[case("-1s 1ns", HDuration::new(1, 1, true))]
+  #[case("1ms", HDuration::new(0, 1000000, false))]
+  #[case("1500ms", HDuration::new(1, 500000000, false))]
+  #[case("1500us", HDuration::new(0, 1500000, false))]
+  #[case("250µs", HDuration::new(0, 250000, false))]
+  #[case("1ms 5μs", HDuration::new(0, 1005000, false))]
   #[case("5m", HDuration::new(300, 0, false))]
   #[case("5h", HDuration::new(18000, 0, false))]
   #[case("5d", HDuration::new(432000, 0, false))]
   #[case("5w", HDuration::new(3024000, 0, false))]
   #[case("3w 5d 2h 10m 7s 1ns", HDuration::new(2254207, 1, false))]
   #[case("3w5d2h", HDuration::new(2253600, 0, false))]
+  #[case("1.5h", HDuration::new(5400, 0, false))]
+  #[case("0.25d", HDuration::new(21600, 0, false))]
+  #[case("2.5s", HDuration::new(2, 500_000_000, false))]
+  #[case("1.5ms 0.5us", HDuration::new(0, 1_500_500, false))]
+  #[case("-.5m", HDuration::new(30, 0, true))]
   fn from_str(#[case] input: &str, #[case] expected: HDuration) {
     assert_eq!(HDuration::from_str(input), Ok(expected))
   }
 
+  #[rstest]
+  #[case("99999999999999999999s")]
+  #[case("1s 18446744073709551616ns")]
+  fn too_large(#[case] input: &str) {
+    assert_eq!(
+      HDuration::from_str(input),
+      Err("Too large of duration".into())
+    )
+  }
+
+  #[rstest]
+  #[case("P3DT4H30M", Ok(HDuration::new(275400, 0, false)))]
+  #[case("PT90S", Ok(HDuration::new(90, 0, false)))]
+  #[case("pt1.5s", Ok(HDuration::new(1, 500_000_000, false)))]
+  #[case("-P1W", Ok(HDuration::new(604800, 0, true)))]
+  #[case("PT0,25H", Ok(HDuration::new(900, 0, false)))]
+  #[case("P1M", Err(()))]
+  #[case("PT", Err(()))]
+  #[case("P", Err(()))]
+  #[case("PT5D", Err(()))]
+  #[case("P5", Err(()))]
+  fn iso(#[case] input: &str, #[case] expected: Result<HDuration, ()>) {
+    assert_eq!(HDuration::from_str(input).map_err(|_| ()), expected)
+  }
+
+  #[rstest]
+  #[case(HDuration::new(0, 0, false), "0s")]
+  #[case(HDuration::new(5400, 0, false), "1h 30m")]
+  #[case(HDuration::new(2254207, 1, false), "3w 5d 2h 10m 7s 1ns")]
+  #[case(HDuration::new(1, 1_500_001, true), "-1s 1ms 500us 1ns")]
+  fn display(#[case] input: HDuration, #[case] expected: &str) {
+    assert_eq!(input.to_string(), expected);
+    assert_eq!(HDuration::from_str(expected), Ok(input))
+  }
+
+  #[rstest]
+  #[case(
+    HDuration::new(180000, 0, false),
+    Precision::Days,
+    Precision::Nanos,
+    "2d 2h"
+  )]
+  #[case(
+    HDuration::new(180000, 0, false),
+    Precision::Hours,
+    Precision::Nanos,
+    "50h"
+  )]
+  #[case(
+    HDuration::new(5400, 0, false),
+    Precision::Mins,
+    Precision::Mins,
+    "90m"
+  )]
+  #[case(
+    HDuration::new(90, 1_500_000, true),
+    Precision::Weeks,
+    Precision::Secs,
+    "-1m 30s"
+  )]
+  #[case(
+    HDuration::new(0, 1_500, false),
+    Precision::Weeks,
+    Precision::Millis,
+    "0s"
+  )]
+  #[case(HDuration::new(30, 0, true), Precision::Hours, Precision::Hours, "0h")]
+  fn between(
+    #[case] input: HDuration,
+    #[case] largest: Precision,
+    #[case] smallest: Precision,
+    #[case] expected: &str,
+  ) {
+    assert_eq!(input.between(largest, smallest), Ok(expected.into()))
+  }
+
+  #[test]
+  fn between_inverted() {
+    assert_eq!(
+      HDuration::new(5400, 0, false).between(Precision::Secs, Precision::Hours),
+      Err("The largest unit, secs, is finer than the smallest, hours".into())
+    )
+  }
+
+  #[rstest]
+  #[case(chrono::Duration::seconds(-90), HDuration::new(90, 0, true))]
+  #[case(
+    chrono::Duration::milliseconds(1500),
+    HDuration::new(1, 500_000_000, false)
+  )]
+  fn from_chrono(#[case] input: chrono::Duration, #[case] expected: HDuration) {
+    assert_eq!(HDuration::from(input), expected)
+  }
+
   #[rstest]
   #[case("1s -1ns")] // Negative must be at front
   #[case("s1")] // Wrong order
   #[case("1 s")]
   #[case("s 1")]
   #[case(" 1s")]
+  #[case("1.5.5s")]
+  #[case("1s.5")]
+  #[case("1.0000000001s")]
   fn invalid_from_str(#[case] input: &str) {
     assert!(HDuration::from_str(input).is_err())
   }
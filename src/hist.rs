@@ -0,0 +1,194 @@
+use std::{
+  collections::BTreeMap,
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{TimeZone, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs},
+  hduration::HDuration,
+  Handler,
+};
+
+/// Past this many rows, a finer bucket is almost certainly a mistake
+const MAX_BUCKETS: i128 = 10_000;
+
+#[derive(Args)]
+pub struct HistArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Width of each bucket. Buckets are aligned to the epoch, so 1h buckets
+  /// start on the hour
+  #[arg(long, short = 'b')]
+  bucket: HDuration,
+
+  /// Draw a bar of up to this many columns beside each count
+  #[arg(long)]
+  bars: Option<usize>,
+
+  /// Times to bucket. When omitted, one time is read per line of stdin
+  #[arg(allow_negative_numbers = true)]
+  input: Vec<ConversionInput>,
+}
+
+/// Counts stamps, in nanoseconds, per bucket start, including empty buckets
+/// between the first and last
+fn bucket(stamps: &[i128], width: i128) -> Vec<(i128, usize)> {
+  let mut counts = BTreeMap::new();
+  for s in stamps {
+    *counts.entry(s.div_euclid(width) * width).or_insert(0) += 1;
+  }
+  let (Some(first), Some(last)) = (counts.keys().next(), counts.keys().last()) else {
+    return Vec::new();
+  };
+  (*first..=*last)
+    .step_by(width as usize)
+    .map(|b| (b, counts.get(&b).copied().unwrap_or(0)))
+    .collect()
+}
+
+impl Handler for HistArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let width = self.bucket.to_chrono().and_then(|d| {
+      d.num_nanoseconds()
+        .filter(|n| *n > 0)
+        .map(i128::from)
+        .ok_or_else(|| format!("Bucket {} must be positive", self.bucket))
+    });
+    let stamps = width.and_then(|w| {
+      ConversionInput::or_stdin(&self.input)?
+        .iter()
+        .map(|inp| inp.to_dt(&self.format.precision, &now))
+        .map(|rdt| {
+          rdt.map(|dt| dt.timestamp() as i128 * 1_000_000_000 + dt.timestamp_subsec_nanos() as i128)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|s| (w, s))
+    });
+    let (width, stamps) = match stamps {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+    let (lo, hi) = match (stamps.iter().min(), stamps.iter().max()) {
+      (Some(lo), Some(hi)) => (*lo, *hi),
+      _ => return writeln!(&mut err, "No times given").map(|_| ExitCode::FAILURE),
+    };
+    if (hi - lo) / width >= MAX_BUCKETS {
+      return writeln!(
+        &mut err,
+        "Over {} buckets of {} are needed, try a wider bucket",
+        MAX_BUCKETS, self.bucket
+      )
+      .map(|_| ExitCode::FAILURE);
+    }
+
+    let rows = bucket(&stamps, width)
+      .into_iter()
+      .map(|(start, count)| {
+        let secs = start.div_euclid(1_000_000_000) as i64;
+        let dt = Utc.timestamp_opt(secs, start.rem_euclid(1_000_000_000) as u32);
+        let label = dt
+          .single()
+          .map(|dt| self.format.format(&dt.with_timezone(&into_tz)))
+          .transpose()?
+          .unwrap_or_default();
+        Ok((label, count))
+      })
+      .collect::<Result<Vec<_>, String>>();
+    let rows = match rows {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(rows) => rows,
+    };
+    let label_width = rows.iter().map(|(l, _)| l.len()).max().unwrap_or(0);
+    let most = rows.iter().map(|(_, c)| *c).max().unwrap_or(0);
+    let count_width = most.to_string().len();
+    for (label, count) in rows {
+      match self.bars {
+        None => writeln!(&mut out, "{:<label_width$}  {:>count_width$}", label, count)?,
+        Some(cols) => {
+          // Any non-empty bucket gets at least one block so it stands out
+          let len = (count * cols).div_ceil(most.max(1));
+          writeln!(
+            &mut out,
+            "{:<label_width$}  {:>count_width$} {}",
+            label,
+            count,
+            "█".repeat(len)
+          )?
+        }
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::bucket;
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[rstest]
+  #[case(vec![], vec![])]
+  #[case(vec![5, 9, 31], vec![(0, 2), (10, 0), (20, 0), (30, 1)])]
+  #[case(vec![-1, 1], vec![(-10, 1), (0, 1)])]
+  fn buckets(#[case] stamps: Vec<i128>, #[case] expected: Vec<(i128, usize)>) {
+    assert_eq!(bucket(&stamps, 10), expected)
+  }
+
+  #[test]
+  fn counts() {
+    let (output, error) =
+      run_test(" hist -p secs -f=%R -b 1h 1679258022 1679259000 1679270000 1679255000 1679258400");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        19:00  1
+        20:00  3
+        21:00  0
+        22:00  0
+        23:00  1
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn bars() {
+    let (output, error) = run_test(" hist -p secs -b 10s --bars 4 0 1 2 3 15 25 26");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        0   4 ████
+        10  1 █
+        20  2 ██
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn too_many() {
+    let (output, error) = run_test(" hist -p secs -b 1ns 0 1");
+    assert_eq!("", output);
+    assert_eq!(
+      "Over 10000 buckets of 1ns are needed, try a wider bucket\n",
+      error
+    );
+  }
+}
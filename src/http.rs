@@ -0,0 +1,207 @@
+use std::{
+  io::{self, Read, Write},
+  net::TcpStream,
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use clap::Args;
+
+use crate::{
+  common::{relative, AtTimezoneArgs, FormatArgs},
+  Handler,
+};
+
+const TIME_HEADERS: [&str; 4] = ["Date", "Expires", "Last-Modified", "Retry-After"];
+
+#[derive(Args)]
+pub struct HttpArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// An http:// URL to request headers from, or - to read a pasted header
+  /// block from stdin (for example the output of curl -sI)
+  #[arg()]
+  source: String,
+}
+
+impl Handler for HttpArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let block = if self.source == "-" {
+      let mut buf = String::new();
+      io::stdin()
+        .read_to_string(&mut buf)
+        .map(|_| buf)
+        .map_err(|e| format!("Could not read stdin: {}", e))
+    } else {
+      fetch_headers(&self.source)
+    };
+    let now = Utc::now();
+    let into_tz = self.timezone.get();
+    let times = block
+      .and_then(|b| header_times(&b, &now))
+      .and_then(|times| {
+        times
+          .into_iter()
+          .map(|(name, dt)| Ok((name, self.format.format(&dt.with_timezone(&into_tz))?, dt)))
+          .collect::<Result<Vec<_>, String>>()
+      });
+    let times = match times {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(t) => t,
+    };
+
+    let width = times
+      .iter()
+      .map(|(name, _, _)| name.len())
+      .max()
+      .unwrap_or(0)
+      + 1;
+    times.iter().try_for_each(|(name, shown, dt)| {
+      writeln!(
+        &mut out,
+        "{:<width$} {} ({})",
+        format!("{}:", name),
+        shown,
+        relative(dt, &now)
+      )
+    })?;
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+/// Extracts the date-bearing headers from a raw header block. Retry-After may be
+/// given in seconds, which is relative to the Date header when present
+fn header_times(
+  block: &str,
+  now: &DateTime<Utc>,
+) -> Result<Vec<(String, DateTime<FixedOffset>)>, String> {
+  let headers = block
+    .lines()
+    .take_while(|l| !l.trim().is_empty())
+    .filter_map(|l| l.split_once(':'))
+    .map(|(k, v)| (k.trim(), v.trim()))
+    .collect::<Vec<_>>();
+  let lookup = |name: &str| {
+    headers
+      .iter()
+      .find(|(k, _)| k.eq_ignore_ascii_case(name))
+      .map(|(_, v)| *v)
+  };
+  let parse = |name: &str, v: &str| {
+    DateTime::parse_from_rfc2822(v).map_err(|e| format!("Could not parse {}: {} ({})", name, v, e))
+  };
+
+  let mut times = Vec::new();
+  for name in TIME_HEADERS {
+    let Some(value) = lookup(name) else {
+      continue;
+    };
+    let dt = match (name, value.parse::<i64>()) {
+      ("Retry-After", Ok(secs)) => {
+        let base = match lookup("Date") {
+          Some(date) => parse("Date", date)?,
+          None => (*now).into(),
+        };
+        base + Duration::seconds(secs)
+      }
+      // Expires: 0 and other invalid dates mean already expired
+      ("Expires", _) => parse(name, value).unwrap_or_else(|_| (*now).into()),
+      _ => parse(name, value)?,
+    };
+    times.push((name.to_string(), dt));
+  }
+  if times.is_empty() {
+    return Err("No Date, Expires, Last-Modified, or Retry-After headers found".into());
+  }
+  Ok(times)
+}
+
+/// Issues a HEAD request for the given http:// URL, returning the raw response
+fn fetch_headers(url: &str) -> Result<String, String> {
+  let rest = url.strip_prefix("http://").ok_or_else(|| {
+    format!(
+      "Only http:// URLs can be fetched, pipe others in with -: {}",
+      url
+    )
+  })?;
+  let (authority, path) = match rest.find('/') {
+    Some(idx) => rest.split_at(idx),
+    None => (rest, "/"),
+  };
+  let addr = if authority.contains(':') {
+    authority.to_string()
+  } else {
+    format!("{}:80", authority)
+  };
+  let host = authority.split(':').next().unwrap_or(authority);
+  let mut sock = TcpStream::connect(&addr).map_err(|e| format!("Could not connect: {}", e))?;
+  write!(
+    sock,
+    "HEAD {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: epc\r\nConnection: close\r\n\r\n",
+    path, host
+  )
+  .map_err(|e| format!("Could not send request: {}", e))?;
+  let mut response = String::new();
+  sock
+    .read_to_string(&mut response)
+    .map_err(|e| format!("Could not read response: {}", e))?;
+  Ok(response)
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{TimeZone, Utc};
+  use indoc::indoc;
+
+  use super::header_times;
+
+  #[test]
+  fn header_block() {
+    let block = indoc! {"
+      HTTP/1.1 503 Service Unavailable
+      date: Sun, 19 Mar 2023 20:36:26 GMT
+      Last-Modified: Tue, 29 Apr 2025 10:00:00 GMT
+      Retry-After: 120
+      Expires: 0
+
+      <html>Expires: not a header</html>
+    "};
+    let now = Utc.timestamp_opt(1700000000, 0).unwrap();
+    let times = header_times(block, &now)
+      .unwrap()
+      .into_iter()
+      .map(|(k, v)| (k, v.timestamp()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      times,
+      vec![
+        ("Date".to_string(), 1679258186),
+        ("Expires".to_string(), 1700000000),
+        ("Last-Modified".to_string(), 1745920800),
+        ("Retry-After".to_string(), 1679258306),
+      ]
+    );
+  }
+
+  #[test]
+  fn retry_after_date() {
+    let block = "Retry-After: Wed, 21 Oct 2015 07:28:00 GMT\n";
+    let now = Utc.timestamp_opt(1700000000, 0).unwrap();
+    let times = header_times(block, &now).unwrap();
+    assert_eq!(times[0].1.timestamp(), 1445412480);
+  }
+
+  #[test]
+  fn no_headers() {
+    let now = Utc.timestamp_opt(1700000000, 0).unwrap();
+    assert!(header_times("Content-Type: text/html\n", &now).is_err());
+  }
+}
@@ -0,0 +1,249 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{TimeZone, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, DurationArgs, FormatArgs},
+  Handler,
+};
+
+/// The time claims printed, in order
+const CLAIMS: [&str; 3] = ["iat", "nbf", "exp"];
+
+#[derive(Args)]
+pub struct JwtArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// Judge validity as of this time rather than now
+  #[arg(long, allow_negative_numbers = true)]
+  at: Option<ConversionInput>,
+
+  /// The token, whose signature is not verified
+  token: String,
+}
+
+impl Handler for JwtArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let at = match &self.at {
+      None => Ok(now),
+      Some(at) => at
+        .to_dt(&self.format.precision, &now)
+        .map(|dt| dt.with_timezone(&into_tz)),
+    };
+    let resolved = at.and_then(|at| Ok((at, payload(&self.token)?)));
+    let (at, payload) = match resolved {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for name in CLAIMS {
+      let Some(secs) = claim(&payload, name) else {
+        continue;
+      };
+      let Some(dt) = Utc
+        .timestamp_opt(secs.floor() as i64, ((secs - secs.floor()) * 1e9) as u32)
+        .single()
+      else {
+        return writeln!(&mut err, "{} of {} is out of range", name, secs)
+          .map(|_| ExitCode::FAILURE);
+      };
+      let dt = dt.with_timezone(&into_tz);
+      let shown = match self.format.format(&dt) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => shown,
+      };
+      let remaining = dt - at;
+      let note = match name {
+        "exp" if remaining <= chrono::Duration::zero() => {
+          code = ExitCode::FAILURE;
+          self
+            .duration
+            .format(-remaining)
+            .map(|d| format!(" expired {} ago", d))
+        }
+        "exp" => self
+          .duration
+          .format(remaining)
+          .map(|d| format!(" expires in {}", d)),
+        "nbf" if remaining > chrono::Duration::zero() => {
+          code = ExitCode::FAILURE;
+          self
+            .duration
+            .format(remaining)
+            .map(|d| format!(" valid in {}", d))
+        }
+        _ => Ok(String::new()),
+      };
+      let note = match note {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(note) => note,
+      };
+      writeln!(&mut out, "{}: {}{}", name, shown, note)?;
+    }
+    Ok(code)
+  }
+}
+
+/// Decodes the JSON payload of a token, the second of its dot separated parts
+fn payload(token: &str) -> Result<String, String> {
+  let part = token
+    .trim()
+    .split('.')
+    .nth(1)
+    .ok_or("Expected a token of dot separated parts")?;
+  base64url(part)
+    .and_then(|bytes| String::from_utf8(bytes).ok())
+    .ok_or_else(|| "The token payload is not base64url encoded JSON".into())
+}
+
+/// Decodes unpadded base64url, as tokens use
+fn base64url(s: &str) -> Option<Vec<u8>> {
+  let sextets = s
+    .trim_end_matches('=')
+    .bytes()
+    .map(|b| match b {
+      b'A'..=b'Z' => Some(b - b'A'),
+      b'a'..=b'z' => Some(b - b'a' + 26),
+      b'0'..=b'9' => Some(b - b'0' + 52),
+      b'-' | b'+' => Some(62),
+      b'_' | b'/' => Some(63),
+      _ => None,
+    })
+    .collect::<Option<Vec<_>>>()?;
+  if sextets.len() % 4 == 1 {
+    return None;
+  }
+  let bytes = sextets
+    .chunks(4)
+    .flat_map(|chunk| {
+      let bits = chunk
+        .iter()
+        .enumerate()
+        .fold(0u32, |acc, (i, s)| acc | (*s as u32) << (18 - 6 * i));
+      let len = chunk.len() - 1;
+      bits.to_be_bytes()[1..=len].to_vec()
+    })
+    .collect();
+  Some(bytes)
+}
+
+/// Reads a numeric member of the top level JSON object, skipping any nested
+/// objects, arrays, and strings that might mention the same name
+fn claim(json: &str, name: &str) -> Option<f64> {
+  let mut depth = 0;
+  let mut chars = json.char_indices().peekable();
+  while let Some((_, c)) = chars.next() {
+    match c {
+      '{' | '[' => depth += 1,
+      '}' | ']' => depth -= 1,
+      '"' => {
+        let mut key = String::new();
+        while let Some((_, c)) = chars.next() {
+          match c {
+            '\\' => key.extend(chars.next().map(|(_, c)| c)),
+            '"' => break,
+            c => key.push(c),
+          }
+        }
+        let after = chars.peek().map_or(json.len(), |(i, _)| *i);
+        let rest = json[after..].trim_start();
+        let is_member = depth == 1 && key == name && rest.starts_with(':');
+        if is_member {
+          let value = rest[1..].trim_start();
+          let end = value
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(value.len());
+          return value[..end].parse().ok();
+        }
+      }
+      _ => {}
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod test {
+  use std::process::ExitCode;
+
+  use indoc::indoc;
+  use rstest::*;
+
+  use super::{base64url, claim};
+  use crate::run_test_code;
+
+  /// {"sub":"1234567890","iat":1679258022,"nbf":1679258022,"exp":1679261622}
+  const TOKEN: &str = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwiaWF0IjoxNjc5MjU4MDIyLCJuYmYiOjE2NzkyNTgwMjIsImV4cCI6MTY3OTI2MTYyMn0.c2ln";
+
+  #[rstest]
+  #[case("", Some(""))]
+  #[case("Zg", Some("f"))]
+  #[case("Zm8", Some("fo"))]
+  #[case("Zm9v", Some("foo"))]
+  #[case("Zm9vYg==", Some("foob"))]
+  #[case("Z", None)]
+  #[case("Zm9v!", None)]
+  fn decode(#[case] input: &str, #[case] expected: Option<&str>) {
+    let decoded = base64url(input).map(|b| String::from_utf8(b).unwrap());
+    assert_eq!(decoded.as_deref(), expected)
+  }
+
+  #[rstest]
+  #[case(r#"{"exp":1679261622}"#, Some(1679261622.0))]
+  #[case(r#"{ "exp" : 1.5 , "iat": 2}"#, Some(1.5))]
+  #[case(r#"{"nested":{"exp":1},"note":"\"exp\":2","exp":3}"#, Some(3.0))]
+  #[case(r#"{"expires":1}"#, None)]
+  fn claims(#[case] json: &str, #[case] expected: Option<f64>) {
+    assert_eq!(claim(json, "exp"), expected)
+  }
+
+  #[test]
+  fn valid() {
+    let cmd = format!(" jwt -f=%FT%T%z --at 1679259822000 {}", TOKEN);
+    let (code, output, error) = run_test_code(&cmd);
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        iat: 2023-03-19T20:33:42+0000
+        nbf: 2023-03-19T20:33:42+0000
+        exp: 2023-03-19T21:33:42+0000 expires in 30m
+      "},
+      output
+    );
+    assert_eq!(ExitCode::SUCCESS, code);
+  }
+
+  #[test]
+  fn expired() {
+    let cmd = format!(" jwt -p secs --at 1679265222 {}", TOKEN);
+    let (code, output, _) = run_test_code(&cmd);
+    assert!(output.ends_with("exp: 1679261622 expired 1h ago\n"));
+    assert_eq!(ExitCode::FAILURE, code);
+  }
+
+  #[test]
+  fn fractional_before_epoch() {
+    // {"exp":-1.5}
+    let (code, output, _) = run_test_code(" jwt --at 0 eyJhbGciOiJIUzI1NiJ9.eyJleHAiOi0xLjV9.c2ln");
+    assert_eq!("exp: -1500 expired 1s 500ms ago\n", output);
+    assert_eq!(ExitCode::FAILURE, code);
+  }
+}
@@ -1,14 +1,85 @@
+mod bench;
+mod cal;
+mod calinfo;
+#[cfg(feature = "cert")]
+mod cert;
 mod common;
+mod compare;
+mod config;
 mod convert;
+mod countdown;
+mod cron;
 mod current;
+mod delta;
+mod expires;
+mod explain;
+mod guess;
 mod hduration;
+mod hist;
+mod http;
+mod jwt;
+mod meeting;
+mod oncalendar;
+mod ordinal;
+mod quarter;
+mod range;
+mod replay;
+#[cfg(feature = "self-update")]
+mod selfupdate;
+mod sleep;
+mod snowflake;
+mod stats;
+mod stopwatch;
+mod time;
+mod timeline;
 mod timezone;
+mod until;
+mod uuid;
+mod week;
+mod weekday;
 
-use clap::{Parser, Subcommand};
+use bench::BenchArgs;
+use cal::CalArgs;
+use calinfo::CalInfoArgs;
+#[cfg(feature = "cert")]
+use cert::CertArgs;
+use clap::{CommandFactory, Parser, Subcommand};
+use compare::CompareArgs;
+use config::Config;
 use convert::ConvArgs;
+use countdown::CountdownArgs;
+use cron::CronArgs;
 use current::CurrentArgs;
-use std::io::{self, Write};
+use delta::DeltaArgs;
+use expires::ExpiresArgs;
+use explain::ExplainBitsArgs;
+use guess::GuessArgs;
+use hist::HistArgs;
+use http::HttpArgs;
+use jwt::JwtArgs;
+use meeting::MeetingArgs;
+use oncalendar::OnCalendarArgs;
+use ordinal::OrdinalArgs;
+use quarter::QuarterArgs;
+use range::RangeArgs;
+use replay::ReplayArgs;
+#[cfg(feature = "self-update")]
+use selfupdate::SelfUpdateArgs;
+use sleep::SleepUntilArgs;
+use snowflake::SnowflakeArgs;
+use stats::StatsArgs;
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+use stopwatch::StopwatchArgs;
+use time::TimeArgs;
+use timeline::TimelineArgs;
 use timezone::TzArgs;
+use until::UntilArgs;
+use uuid::UuidArgs;
+use week::WeekArgs;
+use weekday::WeekdayArgs;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -27,26 +98,105 @@ enum Commands {
   Current(CurrentArgs),
   /// Convert a list of epoch timestamps into date strings or vice versa
   Convert(ConvArgs),
+  /// Get the durations between a list of times, or from a single time to now
+  Delta(DeltaArgs),
+  /// Classify how close a time is to expiring, exiting 0 when OK, 1 on WARN,
+  /// 2 on CRIT, and 3 if the time could not be resolved
+  Expires(ExpiresArgs),
+  /// Convert the date headers of an HTTP response into times
+  Http(HttpArgs),
   /// Get information on supported timezones
   Timezone(TzArgs),
-  // TODO: Delta. Eg get diff of N time-likes and print human legible
+  /// Compare two times, exiting 0 when equal, 1 when the first is earlier, 2 when
+  /// it is later, and 3 if either could not be resolved
+  Compare(CompareArgs),
+  /// Print the validity window of an X.509 certificate file or TLS endpoint
+  #[cfg(feature = "cert")]
+  Cert(CertArgs),
+  /// Replace this binary with the latest signed release
+  #[cfg(feature = "self-update")]
+  SelfUpdate(SelfUpdateArgs),
+  /// Generate a series of times between a start and end
+  Range(RangeArgs),
+  /// Print upcoming trigger times of a systemd calendar expression
+  Oncalendar(OnCalendarArgs),
+  /// Print upcoming trigger times of a cron expression, or check one time
+  Cron(CronArgs),
+  /// Show a live countdown to the given time
+  Countdown(CountdownArgs),
+  /// Block until the given time is reached
+  SleepUntil(SleepUntilArgs),
+  /// Time spans across invocations with start, lap, and stop
+  Stopwatch(StopwatchArgs),
+  /// Run a command and report how long it took
+  Time(TimeArgs),
+  /// Measure conversion throughput on synthetic input
+  Bench(BenchArgs),
+  /// Show what a stamp means at each plausible precision
+  Guess(GuessArgs),
+  /// Show how a stamp's bits read at each precision, and which containers it fits
+  ExplainBits(ExplainBitsArgs),
+  /// Get the time remaining until a given time, optionally in business hours
+  Until(UntilArgs),
+  /// Show a weekly meeting's local time for each attendee zone, flagging DST shifts
+  Meeting(MeetingArgs),
+  /// Summarize a set of times with their range, mean, median, and spread
+  Stats(StatsArgs),
+  /// Count times into fixed width buckets, optionally as a bar chart
+  Hist(HistArgs),
+  /// Re-emit timestamped lines with their original spacing, optionally sped up
+  Replay(ReplayArgs),
+  /// Render labeled start/end spans as a proportional timeline
+  Timeline(TimelineArgs),
+  /// Print when time-based UUIDs, versions 1, 6, and 7, were generated
+  Uuid(UuidArgs),
+  /// Print when snowflake IDs, as Twitter, Discord, and Instagram use, were created
+  Snowflake(SnowflakeArgs),
+  /// Print the iat, nbf, and exp claims of a JSON web token, and how long it remains valid
+  Jwt(JwtArgs),
+  /// Print the calendar or fiscal quarter of times, with its first and last instants
+  Quarter(QuarterArgs),
+  /// Print the ISO week of times, with its first and last instants
+  Week(WeekArgs),
+  /// Print the day of the year of times
+  Ordinal(OrdinalArgs),
+  /// Print the day of the week times fall on
+  Weekday(WeekdayArgs),
+  /// Print whether a month's year is a leap year, its length, and its first and last instants
+  CalInfo(CalInfoArgs),
+  /// Render month calendars, highlighting today and the given times
+  Cal(CalArgs),
 }
 
-fn main() -> Result<(), io::Error> {
-  let cli = Cli::parse();
+fn main() -> Result<ExitCode, io::Error> {
   let output = io::stdout();
-  let error = io::stderr();
+  let mut error = io::stderr();
+  let config = match Config::load() {
+    Err(e) => return writeln!(&mut error, "{}", e).map(|_| ExitCode::FAILURE),
+    Ok(c) => c,
+  };
+  for (var, value) in config.defaults() {
+    if std::env::var_os(var).is_none() {
+      std::env::set_var(var, value);
+    }
+  }
+  let subcommands = Cli::command()
+    .get_subcommands()
+    .map(|s| s.get_name().to_string())
+    .chain(["help".to_string()])
+    .collect::<Vec<_>>();
+  let cli = Cli::parse_from(config.route(std::env::args_os().collect(), &subcommands));
   run(cli, output, error)
 }
 
 pub trait Handler {
-  fn handle<W, E>(&self, output: W, error: E) -> Result<(), io::Error>
+  fn handle<W, E>(&self, output: W, error: E) -> Result<ExitCode, io::Error>
   where
     W: Write,
     E: Write;
 }
 
-fn run<W, E>(cli: Cli, output: W, error: E) -> Result<(), io::Error>
+fn run<W, E>(cli: Cli, output: W, error: E) -> Result<ExitCode, io::Error>
 where
   W: Write,
   E: Write,
@@ -55,6 +205,56 @@ where
     Some(Commands::Timezone(tza)) => tza.handle(output, error),
     Some(Commands::Convert(conv)) => conv.handle(output, error),
     Some(Commands::Current(curr)) => curr.handle(output, error),
+    Some(Commands::Delta(delta)) => delta.handle(output, error),
+    Some(Commands::Expires(exp)) => exp.handle(output, error),
+    Some(Commands::Http(http)) => http.handle(output, error),
+    Some(Commands::Timeline(tl)) => tl.handle(output, error),
+    Some(Commands::Compare(cmp)) => cmp.handle(output, error),
+    Some(Commands::Until(until)) => until.handle(output, error),
+    Some(Commands::Guess(guess)) => guess.handle(output, error),
+    Some(Commands::ExplainBits(bits)) => bits.handle(output, error),
+    Some(Commands::Bench(bench)) => bench.handle(output, error),
+    Some(Commands::Range(range)) => range.handle(output, error),
+    Some(Commands::Oncalendar(cal)) => cal.handle(output, error),
+    Some(Commands::Cron(cron)) => cron.handle(output, error),
+    Some(Commands::SleepUntil(sleep)) => sleep.handle(output, error),
+    Some(Commands::Countdown(countdown)) => countdown.handle(output, error),
+    Some(Commands::Stopwatch(sw)) => sw.handle(output, error),
+    Some(Commands::Time(time)) => time.handle(output, error),
+    Some(Commands::Meeting(meeting)) => meeting.handle(output, error),
+    Some(Commands::Stats(stats)) => stats.handle(output, error),
+    Some(Commands::Hist(hist)) => hist.handle(output, error),
+    Some(Commands::Replay(replay)) => replay.handle(output, error),
+    Some(Commands::Uuid(uuid)) => uuid.handle(output, error),
+    Some(Commands::Snowflake(sf)) => sf.handle(output, error),
+    Some(Commands::Jwt(jwt)) => jwt.handle(output, error),
+    Some(Commands::Quarter(quarter)) => quarter.handle(output, error),
+    Some(Commands::Week(week)) => week.handle(output, error),
+    Some(Commands::Ordinal(ordinal)) => ordinal.handle(output, error),
+    Some(Commands::Weekday(weekday)) => weekday.handle(output, error),
+    Some(Commands::CalInfo(info)) => info.handle(output, error),
+    Some(Commands::Cal(cal)) => cal.handle(output, error),
+    #[cfg(feature = "cert")]
+    Some(Commands::Cert(cert)) => cert.handle(output, error),
+    #[cfg(feature = "self-update")]
+    Some(Commands::SelfUpdate(update)) => update.handle(output, error),
     None => cli.current.handle(output, error),
   }
 }
+
+#[cfg(test)]
+fn run_test(cli_str: &str) -> (String, String) {
+  let (_, output, error) = run_test_code(cli_str);
+  (output, error)
+}
+
+#[cfg(test)]
+fn run_test_code(cli_str: &str) -> (ExitCode, String, String) {
+  let mut output = Vec::new();
+  let mut error = Vec::new();
+  let cli = Cli::try_parse_from(cli_str.split(' ')).expect("Could not parse args");
+  let code = run(cli, &mut output, &mut error).expect("Failed to run");
+  let output = String::from_utf8(output).expect("Not UTF-8");
+  let error = String::from_utf8(error).expect("Not UTF-8");
+  (code, output, error)
+}
@@ -0,0 +1,152 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, AutoTz, ConversionInput, Precision, StampPrecision, Zone},
+  Handler,
+};
+
+fn parse_time(s: &str) -> Result<NaiveTime, String> {
+  NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| format!("Expected HH:MM but got: {}", s))
+}
+
+#[derive(Args)]
+pub struct MeetingArgs {
+  /// The zone the meeting is scheduled in
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  /// How many weeks of the meeting to show
+  #[arg(long, short = 'n', default_value_t = 8)]
+  weeks: usize,
+
+  /// Show weeks from this time rather than now. Stamps are read as millis
+  #[arg(long, allow_negative_numbers = true)]
+  from: Option<ConversionInput>,
+
+  /// The weekday the meeting recurs on
+  #[arg()]
+  day: Weekday,
+
+  /// The wall clock time of the meeting, as HH:MM in the -t timezone
+  #[arg(value_parser = parse_time)]
+  time: NaiveTime,
+
+  /// The zones attendees are in, accepting the same names as -t
+  #[arg(required = true)]
+  attendees: Vec<AutoTz>,
+}
+
+impl Handler for MeetingArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let host = self.timezone.get();
+    let now = Utc::now().with_timezone(&host);
+    let from = match &self.from {
+      None => Ok(now),
+      Some(inp) => inp
+        .to_dt(&StampPrecision::Fixed(Precision::Millis), &now)
+        .map(|dt| dt.with_timezone(&host)),
+    };
+    let from = match from {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(dt) => dt.date_naive(),
+    };
+    let ahead = (7 + self.day.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    let first = from + Duration::days(ahead as i64);
+
+    let zones = std::iter::once(host)
+      .chain(self.attendees.iter().map(|a| Zone::from(a.0)))
+      .collect::<Vec<_>>();
+    let mut rows = vec![std::iter::once("week of".to_string())
+      .chain(zones.iter().map(|z| z.to_string()))
+      .collect::<Vec<_>>()];
+    let mut previous: Option<Vec<String>> = None;
+    let mut shifted = false;
+    for week in 0..self.weeks {
+      let date = first + Duration::weeks(week as i64);
+      let Some(start) = host
+        .from_local_datetime(&date.and_time(self.time))
+        .earliest()
+      else {
+        rows.push(vec![date.to_string(), "skipped by DST".into()]);
+        previous = None;
+        continue;
+      };
+      let locals = zones
+        .iter()
+        .map(|z| start.with_timezone(z).format("%a %H:%M").to_string())
+        .collect::<Vec<_>>();
+      let mut row = vec![date.to_string()];
+      for (i, local) in locals.iter().enumerate() {
+        let moved = previous.as_ref().is_some_and(|p| p[i] != *local);
+        shifted |= moved;
+        row.push(format!("{}{}", local, if moved { " *" } else { "" }));
+      }
+      rows.push(row);
+      previous = Some(locals);
+    }
+
+    let widths = (0..zones.len() + 1)
+      .map(|i| {
+        rows
+          .iter()
+          .filter_map(|r| r.get(i))
+          .map(|c| c.len())
+          .max()
+          .unwrap_or(0)
+      })
+      .collect::<Vec<_>>();
+    for row in rows {
+      let line = row
+        .iter()
+        .zip(&widths)
+        .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+        .collect::<Vec<_>>()
+        .join("  ");
+      writeln!(&mut out, "{}", line.trim_end())?;
+    }
+    if shifted {
+      writeln!(
+        &mut out,
+        "* moved since the week before, as DST changed in one zone but not another"
+      )?;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn staggered_dst() {
+    let (output, error) = run_test(
+      " meeting -t=America/New_York -n 5 --from 2023-03-01T00:00:00Z wed 10:00 London Asia/Kolkata",
+    );
+    assert_eq!("", error);
+    // New York springs forward on the 12th, London not until the 26th
+    assert_eq!(
+      indoc! {"
+        week of     America/New_York  Europe/London  Asia/Kolkata
+        2023-03-01  Wed 10:00         Wed 15:00      Wed 20:30
+        2023-03-08  Wed 10:00         Wed 15:00      Wed 20:30
+        2023-03-15  Wed 10:00         Wed 14:00 *    Wed 19:30 *
+        2023-03-22  Wed 10:00         Wed 14:00      Wed 19:30
+        2023-03-29  Wed 10:00         Wed 15:00 *    Wed 19:30
+        * moved since the week before, as DST changed in one zone but not another
+      "},
+      output
+    );
+  }
+}
@@ -0,0 +1,111 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::Utc;
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs, Schedule, Zone},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct OnCalendarArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// How many upcoming trigger times to print
+  #[arg(long, short = 'n', default_value_t = 5)]
+  iterations: usize,
+
+  /// Find trigger times after this time rather than now
+  #[arg(long, allow_negative_numbers = true)]
+  from: Option<ConversionInput>,
+
+  /// A systemd calendar expression, such as "Mon..Fri *-*-* 10:00". Times
+  /// are read in the timezone ending the expression, or the -t timezone
+  #[arg(required = true)]
+  expression: Vec<String>,
+}
+
+impl Handler for OnCalendarArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let schedule = match Schedule::oncalendar(&self.expression.join(" ")) {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(s) => s,
+    };
+    let into_tz = self.timezone.get();
+    let schedule_tz = schedule.zone.map(Zone::from).unwrap_or(into_tz);
+    let now = Utc::now().with_timezone(&into_tz);
+    let from = match &self.from {
+      None => Ok(now.with_timezone(&schedule_tz)),
+      Some(inp) => inp
+        .to_dt(&self.format.precision, &now)
+        .map(|dt| dt.with_timezone(&schedule_tz)),
+    };
+    let mut at = match from {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(dt) => dt,
+    };
+
+    for i in 0..self.iterations {
+      let Some(next) = schedule.next_after(&at) else {
+        if i == 0 {
+          return writeln!(
+            &mut err,
+            "{} has no upcoming trigger time",
+            self.expression.join(" ")
+          )
+          .map(|_| ExitCode::FAILURE);
+        }
+        break;
+      };
+      match self.format.format(&next.with_timezone(&into_tz)) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => writeln!(&mut out, "{}", shown)?,
+      }
+      at = next;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::process::ExitCode;
+
+  use crate::{run_test, run_test_code};
+  use indoc::indoc;
+
+  #[test]
+  fn upcoming() {
+    let (output, error) =
+      run_test(" oncalendar -n 3 -f=%a%_d%t%R --from 2023-03-17T18:00:00Z Mon..Fri *-*-* 10:00");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        Mon20\t10:00
+        Tue21\t10:00
+        Wed22\t10:00
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn never_fires() {
+    let (code, output, error) = run_test_code(" oncalendar *-02-31 00:00");
+    assert_eq!("", output);
+    assert_eq!("*-02-31 00:00 has no upcoming trigger time\n", error);
+    assert_eq!(ExitCode::FAILURE, code);
+  }
+}
@@ -0,0 +1,65 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{Datelike, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct OrdinalArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Times to print the day of the year of, counting January 1st as 1
+  #[arg(allow_negative_numbers = true, default_value = "now")]
+  input: Vec<ConversionInput>,
+}
+
+impl Handler for OrdinalArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    for input in &self.input {
+      match input.to_dt(&self.format.precision, &now) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(dt) => writeln!(&mut out, "{}", dt.with_timezone(&into_tz).ordinal())?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use indoc::indoc;
+
+  use crate::run_test;
+
+  #[test]
+  fn days() {
+    let (output, error) =
+      run_test(" ordinal -t=UTC -p secs 1689379200 2024-12-31T12:00:00Z 2023-12-31T20:00:00-05:00");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        196
+        366
+        1
+      "},
+      output
+    );
+  }
+}
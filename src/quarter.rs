@@ -0,0 +1,133 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Datelike, Duration, Month, Months, NaiveTime, Utc};
+use clap::Args;
+
+use crate::{
+  common::{relocal, AtTimezoneArgs, ConversionInput, FormatArgs, Zone},
+  Handler,
+};
+
+/// Reads a month by number or name
+fn parse_month(s: &str) -> Result<u32, String> {
+  s.parse::<u32>()
+    .ok()
+    .filter(|m| (1..=12).contains(m))
+    .or_else(|| s.parse::<Month>().ok().map(|m| m.number_from_month()))
+    .ok_or_else(|| format!("Expected a month number or name but got: {}", s))
+}
+
+#[derive(Args)]
+pub struct QuarterArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// The month fiscal years start in, by number or name. Fiscal years are
+  /// named for the calendar year they end in
+  #[arg(long, env = "EPC_FISCAL_START", default_value = "1", value_parser = parse_month)]
+  fiscal_start: u32,
+
+  /// Times to print the quarter of, along with the quarter's first and last
+  /// instants
+  #[arg(allow_negative_numbers = true, default_value = "now")]
+  input: Vec<ConversionInput>,
+}
+
+/// The quarter holding the time, as its label and first and last instants
+fn quarter(
+  dt: &DateTime<Zone>,
+  fiscal_start: u32,
+) -> Result<(String, DateTime<Zone>, DateTime<Zone>), String> {
+  let date = dt.date_naive();
+  let into_year = (date.month0() + 12 - (fiscal_start - 1)) % 12;
+  let number = into_year / 3 + 1;
+  let label = match fiscal_start {
+    1 => format!("{}-Q{}", date.year(), number),
+    _ => {
+      let ends_next_year = date.month() >= fiscal_start;
+      format!("FY{}-Q{}", date.year() + ends_next_year as i32, number)
+    }
+  };
+  let first = date
+    .with_day(1)
+    .and_then(|d| d.checked_sub_months(Months::new(into_year % 3)));
+  let next = first.and_then(|d| d.checked_add_months(Months::new(3)));
+  let (Some(first), Some(next)) = (first, next) else {
+    return Err(format!("The quarter of {} is out of range", dt));
+  };
+  let start = relocal(dt, &first.and_time(NaiveTime::MIN))?;
+  let end = relocal(dt, &next.and_time(NaiveTime::MIN))? - Duration::nanoseconds(1);
+  Ok((label, start, end))
+}
+
+impl Handler for QuarterArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    for input in &self.input {
+      let quartered = input
+        .to_dt(&self.format.precision, &now)
+        .and_then(|dt| quarter(&dt.with_timezone(&into_tz), self.fiscal_start))
+        .and_then(|(label, start, end)| {
+          Ok((
+            label,
+            self.format.format(&start)?,
+            self.format.format(&end)?,
+          ))
+        });
+      match quartered {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok((label, start, end)) => writeln!(&mut out, "{} {} {}", label, start, end)?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::parse_month;
+  use crate::run_test;
+
+  #[rstest]
+  #[case("7", Ok(7))]
+  #[case("jul", Ok(7))]
+  #[case("October", Ok(10))]
+  #[case("13", Err(()))]
+  fn month(#[case] input: &str, #[case] expected: Result<u32, ()>) {
+    assert_eq!(parse_month(input).map_err(|_| ()), expected)
+  }
+
+  #[rstest]
+  #[case("", "2023-Q3 2023-07-01T00:00:00 2023-09-30T23:59:59")]
+  #[case(
+    " --fiscal-start jul",
+    "FY2024-Q1 2023-07-01T00:00:00 2023-09-30T23:59:59"
+  )]
+  #[case(
+    " --fiscal-start oct",
+    "FY2023-Q4 2023-07-01T00:00:00 2023-09-30T23:59:59"
+  )]
+  #[case(
+    " --fiscal-start feb",
+    "FY2024-Q2 2023-05-01T00:00:00 2023-07-31T23:59:59"
+  )]
+  fn quarters(#[case] flags: &str, #[case] expected: &str) {
+    let cmd = format!(" quarter -t=UTC -f=%FT%T{} 2023-07-15T12:00:00Z", flags);
+    let (output, error) = run_test(&cmd);
+    assert_eq!("", error);
+    assert_eq!(format!("{}\n", expected), output);
+  }
+}
@@ -0,0 +1,140 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::Utc;
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, BoundsArgs, ConversionInput, FormatArgs},
+  hduration::HDuration,
+  Handler,
+};
+
+#[derive(Args)]
+pub struct RangeArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  bounds: BoundsArgs,
+
+  /// How far apart each time in the series is. Negative steps count down
+  /// from start to end
+  #[arg(long, short = 's', allow_hyphen_values = true)]
+  step: HDuration,
+
+  /// The first time in the series
+  #[arg(allow_negative_numbers = true)]
+  start: ConversionInput,
+
+  /// The time the series stops at, included when a step lands on it unless
+  /// bounds are half-open
+  #[arg(allow_negative_numbers = true)]
+  end: ConversionInput,
+}
+
+impl Handler for RangeArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let resolved = self
+      .start
+      .to_dt(&self.format.precision, &now)
+      .and_then(|a| Ok((a, self.end.to_dt(&self.format.precision, &now)?)))
+      .and_then(|(a, b)| Ok((a, b, self.step.to_chrono()?)));
+    let (start, end, step) = match resolved {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+    let ascending = start <= end;
+    if step.is_zero() || (start != end && ascending == self.step.negative) {
+      return writeln!(&mut err, "A step of {} never reaches the end", self.step)
+        .map(|_| ExitCode::FAILURE);
+    }
+
+    let mut code = ExitCode::SUCCESS;
+    let start = start.with_timezone(&into_tz);
+    let mut next = Some(start);
+    let end = end.with_timezone(&into_tz);
+    while let Some(dt) = next.filter(|dt| self.bounds.contains(&start, &end, dt)) {
+      match self.format.format(&dt) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => writeln!(&mut out, "{}", shown)?,
+      }
+      if let Some(flag) = self.format.js_unsafe(&dt) {
+        writeln!(&mut err, "{}", flag)?;
+        code = ExitCode::FAILURE;
+      }
+      next = dt.checked_add_signed(step);
+    }
+    if next.is_some_and(|dt| self.bounds.cuts(&dt, &end)) {
+      match self.format.format(&end) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => writeln!(
+          &mut err,
+          "Left out the end {}, as bounds are half-open",
+          shown
+        )?,
+      }
+    }
+    Ok(code)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn hourly() {
+    let (output, error) =
+      run_test(" range -t=America/New_York -f=%FT%R%:z 2023-03-12 2023-03-12T04:00:00 --step 1h");
+    assert_eq!("", error);
+    // Spring forward skips the 2am hour
+    assert_eq!(
+      indoc! {"
+        2023-03-12T00:00-05:00
+        2023-03-12T01:00-05:00
+        2023-03-12T03:00-04:00
+        2023-03-12T04:00-04:00
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn descending() {
+    let (output, error) = run_test(" range -p secs 100 -50 -s -1m");
+    assert_eq!("", error);
+    assert_eq!("100\n40\n-20\n", output);
+  }
+
+  #[test]
+  fn half_open() {
+    let (output, error) = run_test(" range -p secs --bounds half-open 100 -50 -s -75s");
+    assert_eq!("Left out the end -50, as bounds are half-open\n", error);
+    assert_eq!("100\n25\n", output);
+
+    // Ends the steps pass over are left out either way, so go unmentioned
+    let (output, error) = run_test(" range -p secs --bounds half-open 100 -50 -s -60s");
+    assert_eq!("", error);
+    assert_eq!("100\n40\n-20\n", output);
+  }
+
+  #[test]
+  fn wrong_direction() {
+    let (output, error) = run_test(" range -p secs 100 200 -s -1m");
+    assert_eq!("", output);
+    assert_eq!("A step of -1m never reaches the end\n", error);
+  }
+}
@@ -0,0 +1,214 @@
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader, Write},
+  path::PathBuf,
+  process::ExitCode,
+  str::FromStr,
+  thread,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, AutoTz, ConversionInput, Precision, StampPrecision, Zone},
+  hduration::HDuration,
+  Handler,
+};
+
+/// How many times faster than recorded lines are replayed
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Speed(f64);
+
+impl FromStr for Speed {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.strip_suffix('x')
+      .unwrap_or(s)
+      .parse::<f64>()
+      .ok()
+      .filter(|v| v.is_finite() && *v > 0.0)
+      .map(Speed)
+      .ok_or_else(|| format!("Expected a positive speed like 10x but got: {}", s))
+  }
+}
+
+#[derive(Args)]
+pub struct ReplayArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  /// What precision timestamps leading each line should be treated as
+  #[arg(value_enum, long, short, default_value_t = StampPrecision::Fixed(Precision::Millis))]
+  precision: StampPrecision,
+
+  /// Replay this many times faster than recorded, such as 10x or 0.5x
+  #[arg(long, short = 's', default_value = "1x")]
+  speed: Speed,
+
+  /// Cap any single pause at this long, after scaling by speed
+  #[arg(long, short = 'm')]
+  max_gap: Option<HDuration>,
+
+  /// Read each line's time in the zone or offset named by this whitespace
+  /// separated field, counting from 1. Lines with an unknown zone use --tz
+  #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+  tz_from_field: Option<u16>,
+
+  /// File of lines each starting with a time. Reads stdin when omitted
+  #[arg()]
+  file: Option<PathBuf>,
+}
+
+/// The time leading a line, read in the zone its `tz_field` names if any
+fn stamp_of(
+  line: &str,
+  precision: &StampPrecision,
+  now: &DateTime<Zone>,
+  tz_field: Option<u16>,
+) -> Option<DateTime<FixedOffset>> {
+  let fields = line.split_whitespace().collect::<Vec<_>>();
+  let first = fields.first()?;
+  let mut inp = first.parse::<ConversionInput>().ok()?;
+  let zone = tz_field.and_then(|f| fields.get(f as usize - 1));
+  if let Some(zone) = zone {
+    if let Ok(AutoTz(tz)) = zone.parse::<AutoTz>() {
+      inp = ConversionInput::Zoned(Box::new(inp), tz.into());
+    } else if let Ok(offset) = format!("{}{}", first, zone).parse::<ConversionInput>() {
+      // A naive time followed by an offset like +02:00 reads as one time
+      inp = offset;
+    }
+  }
+  inp.to_dt(precision, now).ok()
+}
+
+/// Writes each line once the scaled gap since the previous stamped line has
+/// passed. Lines without a leading time, or stepping backwards, go out at once
+fn replay<R: BufRead, W: Write>(
+  lines: R,
+  mut out: W,
+  stamp_of: impl Fn(&str) -> Option<DateTime<FixedOffset>>,
+  speed: Speed,
+  max_gap: Option<Duration>,
+  mut sleep: impl FnMut(Duration),
+) -> io::Result<()> {
+  let mut prev: Option<DateTime<FixedOffset>> = None;
+  for line in lines.lines() {
+    let line = line?;
+    if let Some(dt) = stamp_of(&line) {
+      if let Some(gap) = prev.map(|p| dt - p).filter(|g| *g > Duration::zero()) {
+        let nanos = gap.num_nanoseconds().unwrap_or(i64::MAX) as f64 / speed.0;
+        let mut pause = Duration::nanoseconds(nanos.min(i64::MAX as f64) as i64);
+        if let Some(max) = max_gap {
+          pause = pause.min(max);
+        }
+        sleep(pause);
+      }
+      prev = Some(dt);
+    }
+    writeln!(out, "{}", line)?;
+    out.flush()?;
+  }
+  Ok(())
+}
+
+impl Handler for ReplayArgs {
+  fn handle<W, E>(&self, out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let now = Utc::now().with_timezone(&self.timezone.get());
+    let max_gap = match self.max_gap.as_ref().map(|m| m.to_chrono()).transpose() {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(m) => m,
+    };
+    let stamp = |line: &str| stamp_of(line, &self.precision, &now, self.tz_from_field);
+    let sleep = |pause: Duration| thread::sleep(pause.to_std().unwrap_or_default());
+    let result = match &self.file {
+      None => replay(io::stdin().lock(), out, stamp, self.speed, max_gap, sleep),
+      Some(path) => match File::open(path) {
+        Err(e) => {
+          return writeln!(&mut err, "Could not open {}: {}", path.display(), e)
+            .map(|_| ExitCode::FAILURE)
+        }
+        Ok(f) => replay(BufReader::new(f), out, stamp, self.speed, max_gap, sleep),
+      },
+    };
+    result.map(|_| ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::io::Cursor;
+
+  use chrono::{Duration, Utc};
+  use chrono_tz::Tz;
+  use indoc::indoc;
+  use rstest::*;
+
+  use super::{replay, stamp_of, Speed};
+  use crate::common::{Precision, StampPrecision, Zone};
+
+  const LOG: &str = indoc! {"
+    1679258000 start
+    1679258010 ten seconds later
+      continued without a stamp
+    1679258005 out of order
+    1679258065 a minute on
+  "};
+
+  fn pauses(speed: f64, max_gap: Option<Duration>) -> (String, Vec<Duration>) {
+    let mut out = Vec::new();
+    let mut slept = Vec::new();
+    let now = Utc::now().with_timezone(&Zone::from(Tz::UTC));
+    let precision = StampPrecision::Fixed(Precision::Secs);
+    replay(
+      Cursor::new(LOG),
+      &mut out,
+      |line| stamp_of(line, &precision, &now, None),
+      Speed(speed),
+      max_gap,
+      |d| slept.push(d),
+    )
+    .unwrap();
+    (String::from_utf8(out).unwrap(), slept)
+  }
+
+  #[test]
+  fn realtime() {
+    let (output, slept) = pauses(1.0, None);
+    assert_eq!(LOG, output);
+    assert_eq!(vec![Duration::seconds(10), Duration::seconds(60)], slept);
+  }
+
+  #[test]
+  fn faster_and_capped() {
+    let (_, slept) = pauses(10.0, Some(Duration::seconds(5)));
+    assert_eq!(vec![Duration::seconds(1), Duration::seconds(5)], slept);
+  }
+
+  #[rstest]
+  #[case("2023-03-19T16:00:00 NYC", "2023-03-19T16:00:00-04:00")]
+  #[case("2023-03-19T16:00:00 europe/berlin", "2023-03-19T16:00:00+01:00")]
+  #[case("2023-03-19T16:00:00 +05:30", "2023-03-19T16:00:00+05:30")]
+  #[case("2023-03-19T16:00:00 Atlantis", "2023-03-19T16:00:00+00:00")]
+  #[case("2023-03-19T16:00:00Z tokyo", "2023-03-20T01:00:00+09:00")]
+  fn zone_field(#[case] line: &str, #[case] expected: &str) {
+    let now = Utc::now().with_timezone(&Zone::from(Tz::UTC));
+    let precision = StampPrecision::Fixed(Precision::Secs);
+    let stamp = stamp_of(line, &precision, &now, Some(2));
+    assert_eq!(stamp.map(|dt| dt.to_rfc3339()), Some(expected.into()))
+  }
+
+  #[rstest]
+  #[case("10x", Ok(Speed(10.0)))]
+  #[case("0.5", Ok(Speed(0.5)))]
+  #[case("0x", Err(()))]
+  #[case("fast", Err(()))]
+  fn speed(#[case] input: &str, #[case] expected: Result<Speed, ()>) {
+    assert_eq!(input.parse::<Speed>().map_err(|_| ()), expected)
+  }
+}
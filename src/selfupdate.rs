@@ -0,0 +1,236 @@
+use std::{
+  env, fs,
+  io::{self, Read, Write},
+  process::ExitCode,
+};
+
+use clap::Args;
+use minisign_verify::{PublicKey, Signature};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::Handler;
+
+/// The latest release, as GitHub describes it
+const FEED: &str = "https://api.github.com/repos/dfontana/epc/releases/latest";
+
+/// The most bytes read from any one download, so a bad feed cannot fill the disk
+const MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// The release asset listing each artifact's SHA-256, as sha256sum writes it
+const SUMS: &str = "SHA256SUMS";
+
+#[derive(Deserialize)]
+struct Release {
+  tag_name: String,
+  assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+  name: String,
+  browser_download_url: String,
+}
+
+impl Release {
+  fn download(&self, name: &str) -> Result<Vec<u8>, String> {
+    let asset = self
+      .assets
+      .iter()
+      .find(|a| a.name == name)
+      .ok_or_else(|| format!("Release {} has no {}", self.tag_name, name))?;
+    download(&asset.browser_download_url)
+  }
+}
+
+#[derive(Args)]
+pub struct SelfUpdateArgs {
+  /// The release feed to check, a GitHub latest release endpoint
+  #[arg(long, default_value = FEED)]
+  feed: String,
+
+  /// The minisign public key releases must be signed with
+  #[arg(long)]
+  key: Option<String>,
+
+  /// Only report whether a newer release exists
+  #[arg(long)]
+  check: bool,
+}
+
+impl SelfUpdateArgs {
+  /// Replaces the running binary with the latest release, describing the outcome
+  fn update(&self) -> Result<String, String> {
+    let current = env!("CARGO_PKG_VERSION");
+    let release = download(&self.feed).and_then(|body| {
+      serde_json::from_slice::<Release>(&body)
+        .map_err(|e| format!("Could not read the release feed: {}", e))
+    })?;
+    let latest = release.tag_name.trim_start_matches('v');
+    if !newer(latest, current)? {
+      return Ok(format!("epc {} is up to date", current));
+    }
+    if self.check {
+      return Ok(format!("epc {} is available, running {}", latest, current));
+    }
+    let key = self
+      .key
+      .as_deref()
+      .ok_or("Pass --key to verify the release before installing it")?;
+
+    let name = artifact();
+    let binary = release.download(&name)?;
+    let sums = release.download(SUMS)?;
+    let signature = release.download(&format!("{}.minisig", name))?;
+    verify_checksum(&binary, &String::from_utf8_lossy(&sums), &name)?;
+    verify_signature(&binary, &String::from_utf8_lossy(&signature), key)?;
+    swap(&binary)?;
+    Ok(format!("Updated epc {} to {}", current, latest))
+  }
+}
+
+impl Handler for SelfUpdateArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    match self.update() {
+      Err(e) => writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(msg) => writeln!(&mut out, "{}", msg).map(|_| ExitCode::SUCCESS),
+    }
+  }
+}
+
+/// The release asset built for this platform, such as epc-x86_64-linux
+fn artifact() -> String {
+  format!(
+    "epc-{}-{}{}",
+    env::consts::ARCH,
+    env::consts::OS,
+    env::consts::EXE_SUFFIX
+  )
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+  let response = ureq::get(url)
+    .set("User-Agent", concat!("epc/", env!("CARGO_PKG_VERSION")))
+    .call()
+    .map_err(|e| format!("Could not fetch {}", e))?;
+  let mut body = Vec::new();
+  response
+    .into_reader()
+    .take(MAX_BYTES)
+    .read_to_end(&mut body)
+    .map_err(|e| format!("Could not read {}: {}", url, e))?;
+  Ok(body)
+}
+
+/// Whether the dotted version is later than the current one
+fn newer(version: &str, current: &str) -> Result<bool, String> {
+  let parse = |v: &str| {
+    v.split('.')
+      .map(|part| part.parse::<u64>())
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|_| format!("{} is not a dotted version", v))
+  };
+  Ok(parse(version)? > parse(current)?)
+}
+
+fn verify_checksum(binary: &[u8], sums: &str, name: &str) -> Result<(), String> {
+  let expected = sums
+    .lines()
+    .filter_map(|line| line.split_once(char::is_whitespace))
+    .find(|(_, file)| file.trim().trim_start_matches('*') == name)
+    .map(|(sum, _)| sum.to_ascii_lowercase())
+    .ok_or_else(|| format!("{} lists no checksum for {}", SUMS, name))?;
+  let actual = Sha256::digest(binary)
+    .iter()
+    .map(|b| format!("{:02x}", b))
+    .collect::<String>();
+  match actual == expected {
+    true => Ok(()),
+    false => Err(format!("Checksum mismatch for {}", name)),
+  }
+}
+
+fn verify_signature(binary: &[u8], signature: &str, key: &str) -> Result<(), String> {
+  let key = PublicKey::from_base64(key.trim()).map_err(|e| format!("Invalid key: {}", e))?;
+  let signature = Signature::decode(signature).map_err(|e| format!("Invalid signature: {}", e))?;
+  key
+    .verify(binary, &signature, false)
+    .map_err(|e| format!("Signature verification failed: {}", e))
+}
+
+/// Writes the new binary beside the running one, then renames it into place
+fn swap(binary: &[u8]) -> Result<(), String> {
+  let exe = env::current_exe().map_err(|e| format!("Could not locate epc: {}", e))?;
+  let staged = exe.with_extension("new");
+  fs::write(&staged, binary).map_err(|e| format!("Could not write {}: {}", staged.display(), e))?;
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+      .map_err(|e| format!("Could not make {} executable: {}", staged.display(), e))?;
+  }
+  fs::rename(&staged, &exe).map_err(|e| {
+    let _ = fs::remove_file(&staged);
+    format!("Could not replace {}: {}", exe.display(), e)
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::{newer, verify_checksum, verify_signature};
+
+  const BINARY: &[u8] = b"epc 9.9.9\n";
+  const KEY: &str = "RWQUC0r6qHpk2zpY4NGjGQl5rCLbnNp04CiqEeLQ4x+9jMlU4vcHk2AE";
+  const SIGNATURE: &str = "untrusted comment: signature from epc test key
+RUQUC0r6qHpk2xnEO8cK22kqHUsQNwWkvXleKV0B5bHfmBsqlYyUFIlwJ5i2Cjbtcyd+DH1EmkqI9/6nM2rkRkrhxyAd2xhEcg8=
+trusted comment: timestamp:1679258022\tfile:epc-test
+OlDyaBd2WIDsgv3Bq1n+WN+J+ZWQTN8e3rhJ08J+YdoTlM3nbJlBhVeIezNPTAh/MRsVRlJniRz5E+ZfmL1vBg==
+";
+
+  #[rstest]
+  #[case("0.2.0", "0.1.0", Ok(true))]
+  #[case("0.10.0", "0.9.1", Ok(true))]
+  #[case("0.1.0", "0.1.0", Ok(false))]
+  #[case("0.0.9", "0.1.0", Ok(false))]
+  #[case("nightly", "0.1.0", Err("nightly is not a dotted version".into()))]
+  fn versions(
+    #[case] version: &str,
+    #[case] current: &str,
+    #[case] expected: Result<bool, String>,
+  ) {
+    assert_eq!(newer(version, current), expected)
+  }
+
+  #[rstest]
+  #[case(
+    "1d555a05516a08ffc64785e82ce2b456c6115a2556816aae51e3a5c43bcab8fb  epc-test",
+    Ok(())
+  )]
+  #[case(
+    "1D555A05516A08FFC64785E82CE2B456C6115A2556816AAE51E3A5C43BCAB8FB *epc-test",
+    Ok(())
+  )]
+  #[case(
+    "0000000000000000000000000000000000000000000000000000000000000000  epc-test",
+    Err("Checksum mismatch for epc-test".into())
+  )]
+  #[case(
+    "1d555a05516a08ffc64785e82ce2b456c6115a2556816aae51e3a5c43bcab8fb  epc-other",
+    Err("SHA256SUMS lists no checksum for epc-test".into())
+  )]
+  fn checksums(#[case] sums: &str, #[case] expected: Result<(), String>) {
+    assert_eq!(verify_checksum(BINARY, sums, "epc-test"), expected)
+  }
+
+  #[test]
+  fn signatures() {
+    assert_eq!(verify_signature(BINARY, SIGNATURE, KEY), Ok(()));
+    assert!(verify_signature(b"tampered", SIGNATURE, KEY).is_err());
+  }
+}
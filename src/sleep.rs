@@ -0,0 +1,179 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+  sync::atomic::{AtomicBool, Ordering},
+  thread,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, DurationArgs, FormatArgs},
+  hduration::HDuration,
+  Handler,
+};
+
+/// Set once SIGINT arrives, so the wait can report what was left
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// The longest single sleep, bounding how late an interrupt is noticed
+const TICK: i64 = 250;
+
+#[derive(Debug, PartialEq)]
+enum Wake {
+  Reached,
+  GaveUp,
+  Interrupted,
+}
+
+#[derive(Args)]
+pub struct SleepUntilArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// Give up, exiting 2, if the time is not reached within this long
+  #[arg(long, short = 'm')]
+  max_wait: Option<HDuration>,
+
+  /// The time to wait for. Exits 0 once reached, or 130 if interrupted
+  #[arg(allow_negative_numbers = true)]
+  input: ConversionInput,
+}
+
+/// Sleeps in short ticks until the target, the deadline, or an interrupt
+fn wait_until(
+  target: DateTime<FixedOffset>,
+  deadline: Option<DateTime<Utc>>,
+  interrupted: &AtomicBool,
+  mut sleep: impl FnMut(Duration),
+) -> Wake {
+  loop {
+    let now = Utc::now();
+    if now >= target {
+      return Wake::Reached;
+    }
+    if deadline.is_some_and(|d| now >= d) {
+      return Wake::GaveUp;
+    }
+    if interrupted.load(Ordering::SeqCst) {
+      return Wake::Interrupted;
+    }
+    let mut nap = (target.with_timezone(&Utc) - now).min(Duration::milliseconds(TICK));
+    if let Some(deadline) = deadline {
+      nap = nap.min(deadline - now);
+    }
+    sleep(nap);
+  }
+}
+
+impl Handler for SleepUntilArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let resolved = self
+      .input
+      .to_dt(&self.format.precision, &now)
+      .and_then(|target| {
+        self.duration.check()?;
+        let max_wait = self.max_wait.as_ref().map(|m| m.to_chrono()).transpose()?;
+        let shown = self.format.format(&target.with_timezone(&into_tz))?;
+        Ok((target, max_wait, shown))
+      });
+    let (target, max_wait, shown) = match resolved {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+    let deadline = max_wait.and_then(|m| Utc::now().checked_add_signed(m));
+
+    // Failing to install the handler only costs the remaining time report
+    let _ = ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst));
+    let wake = wait_until(target, deadline, &INTERRUPTED, |nap| {
+      thread::sleep(nap.to_std().unwrap_or_default())
+    });
+    // Checked before waiting, so this always formats
+    let remaining = || {
+      self
+        .duration
+        .format(target.with_timezone(&Utc) - Utc::now())
+        .unwrap_or_else(|e| e)
+    };
+    match wake {
+      Wake::Reached => Ok(ExitCode::SUCCESS),
+      Wake::GaveUp => {
+        writeln!(
+          &mut out,
+          "Gave up waiting for {}, {} remaining",
+          shown,
+          remaining()
+        )?;
+        Ok(ExitCode::from(2))
+      }
+      Wake::Interrupted => {
+        writeln!(&mut out, "Interrupted with {} remaining", remaining())?;
+        Ok(ExitCode::from(130))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::{process::ExitCode, sync::atomic::AtomicBool};
+
+  use chrono::{Duration, Utc};
+
+  use super::{wait_until, Wake};
+  use crate::run_test_code;
+
+  #[test]
+  fn already_passed() {
+    let (code, output, error) = run_test_code(" sleep-until -p secs 0");
+    assert_eq!(("", ""), (output.as_str(), error.as_str()));
+    assert_eq!(ExitCode::SUCCESS, code);
+  }
+
+  #[test]
+  fn gives_up() {
+    let (code, output, error) = run_test_code(" sleep-until -p secs -m 0s -f=%Y 4102444800");
+    assert_eq!("", error);
+    assert!(
+      output.starts_with("Gave up waiting for 2100, "),
+      "{}",
+      output
+    );
+    assert!(output.ends_with(" remaining\n"), "{}", output);
+    assert_eq!(ExitCode::from(2), code);
+  }
+
+  #[test]
+  fn interrupted() {
+    let target = (Utc::now() + Duration::hours(1)).into();
+    let naps = std::cell::Cell::new(0);
+    let wake = wait_until(target, None, &AtomicBool::new(true), |_| {
+      naps.set(naps.get() + 1)
+    });
+    assert_eq!((wake, naps.get()), (Wake::Interrupted, 0));
+  }
+
+  #[test]
+  fn naps_are_short() {
+    let target = (Utc::now() + Duration::hours(1)).into();
+    let interrupted = AtomicBool::new(false);
+    let wake = wait_until(target, None, &interrupted, |nap| {
+      assert!(nap <= Duration::milliseconds(super::TICK));
+      interrupted.store(true, std::sync::atomic::Ordering::SeqCst);
+    });
+    assert_eq!(wake, Wake::Interrupted);
+  }
+}
@@ -0,0 +1,139 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Offset, TimeZone, Utc};
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs},
+  Handler,
+};
+
+/// Where a service's snowflakes count milliseconds from, and how many low
+/// bits hold the worker and sequence below them
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Layout {
+  /// Millis since 2010-11-04T01:42:54.657Z above 22 bits
+  Twitter,
+  /// Millis since 2015-01-01 above 22 bits
+  Discord,
+  /// Millis since 2011-08-24T21:07:01.721Z above 23 bits
+  Instagram,
+}
+
+impl Layout {
+  fn epoch_millis(&self) -> i64 {
+    match self {
+      Layout::Twitter => 1_288_834_974_657,
+      Layout::Discord => 1_420_070_400_000,
+      Layout::Instagram => 1_314_220_021_721,
+    }
+  }
+
+  fn shift(&self) -> u8 {
+    match self {
+      Layout::Twitter | Layout::Discord => 22,
+      Layout::Instagram => 23,
+    }
+  }
+}
+
+#[derive(Args)]
+pub struct SnowflakeArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// The service the snowflakes come from
+  #[arg(value_enum, long, short, default_value_t = Layout::Twitter)]
+  layout: Layout,
+
+  /// A custom epoch the snowflakes count from, in place of the layout's
+  #[arg(long, allow_negative_numbers = true)]
+  epoch: Option<ConversionInput>,
+
+  /// A custom count of low bits below the timestamp, in place of the layout's
+  #[arg(long, value_parser = clap::value_parser!(u8).range(0..64))]
+  shift: Option<u8>,
+
+  /// Snowflake IDs to print the creation time of
+  #[arg(required = true)]
+  ids: Vec<u64>,
+}
+
+impl SnowflakeArgs {
+  fn epoch(&self) -> Result<DateTime<FixedOffset>, String> {
+    match &self.epoch {
+      Some(epoch) => epoch.to_dt(
+        &self.format.precision,
+        &Utc::now().with_timezone(&self.timezone.get()),
+      ),
+      None => Utc
+        .timestamp_millis_opt(self.layout.epoch_millis())
+        .single()
+        .map(|dt| dt.with_timezone(&Utc.fix()))
+        .ok_or_else(|| "Epoch out of range".into()),
+    }
+  }
+}
+
+impl Handler for SnowflakeArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let epoch = match self.epoch() {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(epoch) => epoch,
+    };
+    let shift = self.shift.unwrap_or_else(|| self.layout.shift());
+    let into_tz = self.timezone.get();
+    for id in &self.ids {
+      let millis = (id >> shift) as i64;
+      let Some(dt) = epoch.checked_add_signed(Duration::milliseconds(millis)) else {
+        return writeln!(&mut err, "{} is out of range", id).map(|_| ExitCode::FAILURE);
+      };
+      match self.format.format(&dt.with_timezone(&into_tz)) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => writeln!(&mut out, "{}", shown)?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn layouts() {
+    let (output, error) = run_test(" snowflake -f=%FT%T%.3fZ 1636839441432399872");
+    assert_eq!("", error);
+    assert_eq!("2023-03-17T21:18:28.500Z\n", output);
+
+    let (output, error) = run_test(" snowflake -l discord -f=%FT%T%.3fZ 175928847299117063");
+    assert_eq!("", error);
+    assert_eq!("2016-04-30T11:18:25.796Z\n", output);
+  }
+
+  #[test]
+  fn custom() {
+    let (output, error) =
+      run_test(" snowflake --epoch 2020-01-01T00:00:00Z --shift 12 -f=%FT%T%.3fZ 4096000 8192000");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2020-01-01T00:00:01.000Z
+        2020-01-01T00:00:02.000Z
+      "},
+      output
+    );
+  }
+}
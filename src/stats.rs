@@ -0,0 +1,173 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, DurationArgs, FormatArgs},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct StatsArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// Times to summarize. When omitted, one time is read per line of stdin
+  #[arg(allow_negative_numbers = true)]
+  input: Vec<ConversionInput>,
+}
+
+/// Summary of a set of times, as nanoseconds since the epoch
+#[derive(Debug, PartialEq)]
+struct Summary {
+  count: usize,
+  min: i128,
+  max: i128,
+  mean: i128,
+  median: i128,
+  stddev: f64,
+}
+
+fn nanos(dt: &DateTime<FixedOffset>) -> i128 {
+  dt.timestamp() as i128 * 1_000_000_000 + dt.timestamp_subsec_nanos() as i128
+}
+
+fn summarize(mut stamps: Vec<i128>) -> Option<Summary> {
+  stamps.sort_unstable();
+  let count = stamps.len();
+  let (min, max) = (*stamps.first()?, *stamps.last()?);
+  let mean = stamps.iter().sum::<i128>() / count as i128;
+  let median = if count.is_multiple_of(2) {
+    (stamps[count / 2 - 1] + stamps[count / 2]) / 2
+  } else {
+    stamps[count / 2]
+  };
+  // Deviations are taken from the mean so the sum of squares stays small
+  let variance = stamps
+    .iter()
+    .map(|s| ((s - mean) as f64).powi(2))
+    .sum::<f64>()
+    / count as f64;
+  Some(Summary {
+    count,
+    min,
+    max,
+    mean,
+    median,
+    stddev: variance.sqrt(),
+  })
+}
+
+fn to_duration(nanos: i128) -> Duration {
+  let secs = nanos.div_euclid(1_000_000_000);
+  Duration::seconds(secs as i64) + Duration::nanoseconds(nanos.rem_euclid(1_000_000_000) as i64)
+}
+
+impl Handler for StatsArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let stamps = ConversionInput::or_stdin(&self.input).and_then(|inputs| {
+      inputs
+        .iter()
+        .map(|inp| inp.to_dt(&self.format.precision, &now).map(|dt| nanos(&dt)))
+        .collect::<Result<Vec<_>, _>>()
+    });
+    let summary = match stamps.map(summarize) {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(None) => return writeln!(&mut err, "No times given").map(|_| ExitCode::FAILURE),
+      Ok(Some(s)) => s,
+    };
+
+    // The mean and median lie between valid times, so are valid themselves
+    let time = |ns: i128| {
+      let secs = ns.div_euclid(1_000_000_000) as i64;
+      let dt = Utc.timestamp_opt(secs, ns.rem_euclid(1_000_000_000) as u32);
+      dt.single()
+        .map(|dt| self.format.format(&dt.with_timezone(&into_tz)))
+        .transpose()
+        .map(Option::unwrap_or_default)
+    };
+    let shown = time(summary.min).and_then(|min| {
+      Ok((
+        min,
+        time(summary.max)?,
+        time(summary.mean)?,
+        time(summary.median)?,
+        self
+          .duration
+          .format(to_duration(summary.max - summary.min))?,
+        self
+          .duration
+          .format(to_duration(summary.stddev.round() as i128))?,
+      ))
+    });
+    let (min, max, mean, median, span, stddev) = match shown {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+    writeln!(&mut out, "count:  {}", summary.count)?;
+    writeln!(&mut out, "min:    {}", min)?;
+    writeln!(&mut out, "max:    {}", max)?;
+    writeln!(&mut out, "span:   {}", span)?;
+    writeln!(&mut out, "mean:   {}", mean)?;
+    writeln!(&mut out, "median: {}", median)?;
+    writeln!(&mut out, "stddev: {}", stddev)?;
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::{summarize, Summary};
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[rstest]
+  #[case(vec![5], Summary { count: 1, min: 5, max: 5, mean: 5, median: 5, stddev: 0.0 })]
+  #[case(vec![4, 1, 3, 2], Summary { count: 4, min: 1, max: 4, mean: 2, median: 2, stddev: 1.224744871391589 })]
+  #[case(vec![-10, 10, 30], Summary { count: 3, min: -10, max: 30, mean: 10, median: 10, stddev: 16.32993161855452 })]
+  fn summary(#[case] stamps: Vec<i128>, #[case] expected: Summary) {
+    assert_eq!(summarize(stamps), Some(expected))
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(summarize(vec![]), None)
+  }
+
+  #[test]
+  fn args() {
+    let (output, error) =
+      run_test(" stats -p secs -f=%T 1679258022 1679258082 1679258042 1679258402");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        count:  4
+        min:    20:33:42
+        max:    20:40:02
+        span:   6m 20s
+        mean:   20:35:37
+        median: 20:34:22
+        stddev: 2m 34s 515ms 371us 404ns
+      "},
+      output
+    );
+  }
+}
@@ -0,0 +1,169 @@
+use std::{
+  env, fs,
+  io::{self, Write},
+  path::PathBuf,
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{DurationArgs, Precision},
+  Handler,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Action {
+  /// Start timing, discarding any previous run
+  Start,
+  /// Print the time since the last lap and since starting
+  Lap,
+  /// Print the total time and clear the stopwatch
+  Stop,
+}
+
+#[derive(Args)]
+pub struct StopwatchArgs {
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// Where the stopwatch keeps its state between runs. Defaults to
+  /// $XDG_STATE_HOME/epc/stopwatch
+  #[arg(long)]
+  file: Option<PathBuf>,
+
+  #[arg(value_enum)]
+  action: Action,
+}
+
+/// The start followed by each lap, one nanosecond stamp per line
+fn read_marks(path: &PathBuf) -> Result<Vec<DateTime<Utc>>, String> {
+  let text = fs::read_to_string(path).map_err(|e| match e.kind() {
+    io::ErrorKind::NotFound => "The stopwatch has not been started".to_string(),
+    _ => format!("Could not read {}: {}", path.display(), e),
+  })?;
+  text
+    .lines()
+    .map(|l| {
+      l.parse::<i64>()
+        .map(|ns| Utc.timestamp_nanos(ns))
+        .map_err(|_| format!("Corrupt stopwatch state in {}: {}", path.display(), l))
+    })
+    .collect()
+}
+
+fn write_marks(path: &PathBuf, marks: &[DateTime<Utc>]) -> Result<(), String> {
+  if let Some(dir) = path.parent() {
+    fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+  }
+  let text = marks
+    .iter()
+    .map(|m| Precision::Nanos.as_stamp(m).map(|ns| format!("{}\n", ns)))
+    .collect::<Result<String, String>>()?;
+  fs::write(path, text).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+impl StopwatchArgs {
+  fn path(&self) -> Result<PathBuf, String> {
+    if let Some(file) = &self.file {
+      return Ok(file.clone());
+    }
+    env::var_os("XDG_STATE_HOME")
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state")))
+      .map(|dir| dir.join("epc").join("stopwatch"))
+      .ok_or_else(|| "Could not find a state directory, pass --file".into())
+  }
+
+  /// Applies the action at the given instant, returning what to report
+  fn act(&self, now: DateTime<Utc>) -> Result<String, String> {
+    self.duration.check()?;
+    let path = self.path()?;
+    match self.action {
+      Action::Start => {
+        write_marks(&path, &[now])?;
+        Ok("started".into())
+      }
+      Action::Lap => {
+        let mut marks = read_marks(&path)?;
+        let (first, last) = match (marks.first(), marks.last()) {
+          (Some(f), Some(l)) => (*f, *l),
+          _ => return Err("The stopwatch has not been started".into()),
+        };
+        marks.push(now);
+        write_marks(&path, &marks)?;
+        Ok(format!(
+          "lap {}: {} (total {})",
+          marks.len() - 1,
+          self.duration.format(now - last)?,
+          self.duration.format(now - first)?
+        ))
+      }
+      Action::Stop => {
+        let total = read_marks(&path)?
+          .first()
+          .map(|start| now - *start)
+          .unwrap_or_else(Duration::zero);
+        fs::remove_file(&path).map_err(|e| format!("Could not clear {}: {}", path.display(), e))?;
+        Ok(format!("stopped: {}", self.duration.format(total)?))
+      }
+    }
+  }
+}
+
+impl Handler for StopwatchArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    match self.act(Utc::now()) {
+      Err(e) => writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(report) => writeln!(&mut out, "{}", report).map(|_| ExitCode::SUCCESS),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use chrono::{Duration, TimeZone, Utc};
+  use clap::Parser;
+
+  use crate::{Cli, Commands};
+
+  fn stopwatch(file: &str, action: &str) -> super::StopwatchArgs {
+    let args = ["epc", "stopwatch", "--file", file, action];
+    match Cli::parse_from(args).commands {
+      Some(Commands::Stopwatch(args)) => args,
+      _ => panic!("Not a stopwatch"),
+    }
+  }
+
+  #[test]
+  fn laps() {
+    let file = std::env::temp_dir().join(format!("epc-stopwatch-{}", std::process::id()));
+    let file = file.to_str().unwrap();
+    let start = Utc.timestamp_opt(1679258022, 0).unwrap();
+    let at = |ms| start + Duration::milliseconds(ms);
+
+    assert_eq!(
+      stopwatch(file, "lap").act(start),
+      Err("The stopwatch has not been started".into())
+    );
+    assert_eq!(stopwatch(file, "start").act(start), Ok("started".into()));
+    assert_eq!(
+      stopwatch(file, "lap").act(at(1500)),
+      Ok("lap 1: 1s 500ms (total 1s 500ms)".into())
+    );
+    assert_eq!(
+      stopwatch(file, "lap").act(at(62_000)),
+      Ok("lap 2: 1m 500ms (total 1m 2s)".into())
+    );
+    assert_eq!(
+      stopwatch(file, "stop").act(at(63_000)),
+      Ok("stopped: 1m 3s".into())
+    );
+    assert!(stopwatch(file, "stop").act(at(64_000)).is_err());
+  }
+}
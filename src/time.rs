@@ -0,0 +1,77 @@
+use std::{
+  io::{self, Write},
+  process::{Command, ExitCode},
+  time::Instant,
+};
+
+use clap::Args;
+
+use crate::{common::DurationArgs, Handler};
+
+#[derive(Args)]
+pub struct TimeArgs {
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// The command to run and its arguments, after a --. Its exit code is
+  /// passed through, and the wall clock time it took is written to stderr
+  #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+  command: Vec<String>,
+}
+
+impl Handler for TimeArgs {
+  fn handle<W, E>(&self, _out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let (program, args) = self.command.split_first().expect("clap requires a command");
+    if let Err(e) = self.duration.check() {
+      return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE);
+    }
+    let started = Instant::now();
+    let status = match Command::new(program).args(args).status() {
+      Err(e) => {
+        return writeln!(&mut err, "Could not run {}: {}", program, e).map(|_| ExitCode::from(127))
+      }
+      Ok(status) => status,
+    };
+    let elapsed = chrono::Duration::from_std(started.elapsed())
+      .unwrap_or_else(|_| chrono::Duration::milliseconds(i64::MAX));
+    // Checked before running, so this always formats
+    let elapsed = self.duration.format(elapsed).unwrap_or_else(|e| e);
+    writeln!(&mut err, "{}", elapsed)?;
+    // Killed by a signal, so there is no code to pass on
+    let code = status.code().unwrap_or(1);
+    Ok(ExitCode::from(u8::try_from(code).unwrap_or(1)))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::process::ExitCode;
+
+  use crate::run_test_code;
+
+  #[test]
+  fn passes_exit_code() {
+    let (code, output, error) = run_test_code(" time -d secs -- false");
+    assert_eq!("", output);
+    assert_eq!("0\n", error);
+    assert_eq!(ExitCode::FAILURE, code);
+  }
+
+  #[test]
+  fn human() {
+    let (code, _, error) = run_test_code(" time -- true");
+    assert!(error.ends_with("s\n"), "{}", error);
+    assert_eq!(ExitCode::SUCCESS, code);
+  }
+
+  #[test]
+  fn missing() {
+    let (code, _, error) = run_test_code(" time -- epc-no-such-command");
+    assert!(error.starts_with("Could not run epc-no-such-command: "));
+    assert_eq!(ExitCode::from(127), code);
+  }
+}
@@ -0,0 +1,209 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+  str::FromStr,
+};
+
+use chrono::{DateTime, FixedOffset, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs, StampPrecision, Zone},
+  Handler,
+};
+
+#[derive(Clone)]
+struct Span {
+  label: String,
+  start: ConversionInput,
+  end: ConversionInput,
+}
+
+impl FromStr for Span {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (label, range) = s
+      .split_once('=')
+      .ok_or_else(|| format!("Expected label=start,end but got: {}", s))?;
+    let (start, end) = range
+      .split_once(',')
+      .ok_or_else(|| format!("Expected label=start,end but got: {}", s))?;
+    Ok(Span {
+      label: label.into(),
+      start: start.parse()?,
+      end: end.parse()?,
+    })
+  }
+}
+
+impl Span {
+  fn resolve(
+    &self,
+    precision: &StampPrecision,
+    now: &DateTime<Zone>,
+  ) -> Result<(DateTime<FixedOffset>, DateTime<FixedOffset>), String> {
+    let start = self.start.to_dt(precision, now)?;
+    let end = self.end.to_dt(precision, now)?;
+    if end < start {
+      return Err(format!("{} ends before it starts", self.label));
+    }
+    Ok((start, end))
+  }
+}
+
+#[derive(Args)]
+pub struct TimelineArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Number of columns the bars are scaled into
+  #[arg(long, short = 'w', default_value_t = 60)]
+  width: usize,
+
+  /// Labeled spans to render, given as label=start,end. Start and end
+  /// accept the same inputs as convert
+  #[arg(required = true)]
+  spans: Vec<Span>,
+}
+
+impl Handler for TimelineArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let maybe_spans = self
+      .spans
+      .iter()
+      .map(|s| s.resolve(&self.format.precision, &now))
+      .collect::<Result<Vec<_>, _>>();
+    let spans = match maybe_spans {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(spans) => spans,
+    };
+    if self.width == 0 {
+      return writeln!(&mut err, "Width must be at least 1").map(|_| ExitCode::FAILURE);
+    }
+
+    // Spans are non-empty, as clap requires at least one
+    let min = spans.iter().map(|(s, _)| *s).min().unwrap();
+    let max = spans.iter().map(|(_, e)| *e).max().unwrap();
+    let total = (max - min).num_nanoseconds().unwrap_or(i64::MAX).max(1) as f64;
+    let axis = self
+      .format
+      .format(&min.with_timezone(&into_tz))
+      .and_then(|first| Ok((first, self.format.format(&max.with_timezone(&into_tz))?)));
+    let (first, last) = match axis {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(v) => v,
+    };
+    // Widths count chars, as the padding of format strings does
+    let label_width = self
+      .spans
+      .iter()
+      .map(|s| s.label.chars().count())
+      .max()
+      .unwrap_or(0);
+
+    for (span, (start, end)) in self.spans.iter().zip(spans.iter()) {
+      let scale = |dt: &DateTime<FixedOffset>| {
+        let offset = (*dt - min).num_nanoseconds().unwrap_or(i64::MAX) as f64;
+        offset / total * self.width as f64
+      };
+      let from = (scale(start).floor() as usize).min(self.width - 1);
+      let to = (scale(end).ceil() as usize).clamp(from + 1, self.width);
+      writeln!(
+        &mut out,
+        "{:<label_width$} │{}{}{}│",
+        span.label,
+        " ".repeat(from),
+        "█".repeat(to - from),
+        " ".repeat(self.width - to),
+      )?;
+    }
+
+    // Render the axis, pushing the end label to its own line if both won't fit
+    let pad = " ".repeat(label_width + 1);
+    writeln!(&mut out, "{}└{}┘", pad, "─".repeat(self.width))?;
+    let axis_width = self.width + 2;
+    let (first_width, last_width) = (first.chars().count(), last.chars().count());
+    if first_width + last_width < axis_width {
+      let gap = axis_width - first_width - last_width;
+      writeln!(&mut out, "{}{}{}{}", pad, first, " ".repeat(gap), last)?;
+    } else {
+      writeln!(&mut out, "{}{}", pad, first)?;
+      writeln!(&mut out, "{}{:>axis_width$}", pad, last)?;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[test]
+  fn proportional_bars() {
+    let (output, error) =
+      run_test(" timeline -p secs -w 20 build=1679258000,1679258040 deploy=1679258040,1679258100");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        build  │████████            │
+        deploy │        ████████████│
+               └────────────────────┘
+               1679258000  1679258100
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn formatted_axis() {
+    let (output, error) = run_test(
+      " timeline -p secs -w 4 -t=America/New_York -f=%H:%M a=1679258000,1679258060 b=1679258030,1679258060",
+    );
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        a │████│
+        b │  ██│
+          └────┘
+          16:33
+           16:34
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn wide_chars() {
+    let (output, error) = run_test(
+      " timeline -p secs -w 12 -f=%H:%M→ café=1679258000,1679258040 ü=1679258040,1679258100",
+    );
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        café │█████       │
+        ü    │    ████████│
+             └────────────┘
+             20:33→  20:35→
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn end_before_start() {
+    let (output, error) = run_test(" timeline -p secs bad=1679258100,1679258000");
+    assert_eq!("", output);
+    assert_eq!("bad ends before it starts\n", error);
+  }
+}
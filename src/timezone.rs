@@ -1,21 +1,459 @@
-use std::io::{self, Write};
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
 
-use chrono_tz::TZ_VARIANTS;
-use clap::Args;
+use chrono::{DateTime, Datelike, Duration, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, Tz, TZ_VARIANTS};
+use clap::{Args, ValueEnum};
 
-use crate::Handler;
+use crate::{
+  common::{
+    place_candidates, AutoTz, ConversionInput, FormatArgs, Precision, StampPrecision, Zone,
+    DEFAULT_FORMAT,
+  },
+  Handler,
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ZoneOrder {
+  /// Alphabetically by name
+  Name,
+  /// West to east by current UTC offset, then by name
+  Offset,
+}
 
 #[derive(Args)]
-pub struct TzArgs {}
+pub struct TzArgs {
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Resolve place names, aliases, or abbreviations into the IANA zones they
+  /// could refer to instead of listing all zones
+  #[arg(long, num_args = 1..)]
+  resolve: Vec<String>,
+
+  /// Only list zones containing this text, ignoring case. Globs with * and ?
+  /// match the whole name instead
+  #[arg(conflicts_with = "resolve")]
+  filter: Option<String>,
+
+  /// Show each zone's current UTC offset, abbreviation, and whether DST is in
+  /// effect
+  #[arg(long, short = 'l')]
+  long: bool,
+
+  /// Show the current time, or the --at time, in each zone as a world clock.
+  /// Times print with the -f default unless another format is given
+  #[arg(long)]
+  now: bool,
+
+  /// List the zones using this abbreviation at some point this year, such
+  /// as IST or CET, with the offset it stands for in each
+  #[arg(long, conflicts_with_all = ["resolve", "long", "now"])]
+  abbr: Option<String>,
+
+  /// List each change of UTC offset or abbreviation in this zone, with the
+  /// offsets before and after
+  #[arg(long, conflicts_with_all = ["resolve", "long", "now", "abbr", "filter"])]
+  transitions: Option<AutoTz>,
+
+  /// The year to list transitions in, defaulting to the current one
+  #[arg(long, requires = "transitions")]
+  year: Option<i32>,
+
+  /// Describe zones as of this time rather than now, showing the offset,
+  /// abbreviation, and DST status each had then. Stamps are read in the -p
+  /// precision, or inferred from their size when -p is left at millis
+  #[arg(long, allow_negative_numbers = true, conflicts_with_all = ["resolve", "transitions"])]
+  at: Option<ConversionInput>,
+
+  /// How to order the listed zones
+  #[arg(value_enum, long, default_value_t = ZoneOrder::Name)]
+  sort: ZoneOrder,
+}
+
+/// Zone offsets at an instant, in seconds east of UTC
+fn offset_at(tz: &Tz, at: &DateTime<Utc>) -> i32 {
+  tz.offset_from_utc_datetime(&at.naive_utc())
+    .fix()
+    .local_minus_utc()
+}
+
+/// The offset, abbreviation, and DST status of a zone at an instant
+fn describe(tz: &Tz, at: &DateTime<Utc>) -> [String; 3] {
+  let offset = tz.offset_from_utc_datetime(&at.naive_utc());
+  let dst = if offset.dst_offset().is_zero() {
+    "-"
+  } else {
+    "DST"
+  };
+  [
+    offset.fix().to_string(),
+    offset.to_string(),
+    dst.to_string(),
+  ]
+}
+
+/// The offsets a zone uses the abbreviation for across the year of the given
+/// instant, checked in mid winter and mid summer as well as at the instant
+fn abbr_offsets(tz: &Tz, abbr: &str, at: &DateTime<Utc>) -> Vec<String> {
+  let year = at.year();
+  let seasons = [1, 7]
+    .iter()
+    .filter_map(|m| Utc.with_ymd_and_hms(year, *m, 15, 12, 0, 0).single());
+  let mut offsets = Vec::new();
+  for instant in seasons.chain([*at]) {
+    let offset = tz.offset_from_utc_datetime(&instant.naive_utc());
+    let fixed = offset.fix().to_string();
+    if offset.to_string().eq_ignore_ascii_case(abbr) && !offsets.contains(&fixed) {
+      offsets.push(fixed);
+    }
+  }
+  offsets
+}
+
+/// The instants within a year where the zone's offset or abbreviation changes,
+/// found by stepping hourly then narrowing each change to the second
+fn transitions(tz: &Tz, year: i32) -> Vec<DateTime<Utc>> {
+  let label = |at: &DateTime<Utc>| {
+    let offset = tz.offset_from_utc_datetime(&at.naive_utc());
+    (offset.fix(), offset.to_string())
+  };
+  let (Some(start), Some(end)) = (
+    Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).single(),
+    Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0).single(),
+  ) else {
+    return Vec::new();
+  };
+  let mut found = Vec::new();
+  let mut at = start;
+  while at < end {
+    let next = (at + Duration::hours(1)).min(end);
+    if label(&at) != label(&next) {
+      // The change lies in (lo, hi], so narrow until they are a second apart
+      let (mut lo, mut hi) = (at, next);
+      while hi - lo > Duration::seconds(1) {
+        let mid = lo + (hi - lo) / 2;
+        if label(&mid) == label(&lo) {
+          lo = mid;
+        } else {
+          hi = mid;
+        }
+      }
+      found.push(hi);
+    }
+    at = next;
+  }
+  found
+}
+
+/// Matches a glob of * and ? wildcards against the whole text
+fn glob(pattern: &[char], text: &[char]) -> bool {
+  match (pattern.first(), text.first()) {
+    (None, _) => text.is_empty(),
+    (Some('*'), _) => glob(&pattern[1..], text) || (!text.is_empty() && glob(pattern, &text[1..])),
+    (Some(_), None) => false,
+    (Some('?'), Some(_)) => glob(&pattern[1..], &text[1..]),
+    (Some(p), Some(t)) => p == t && glob(&pattern[1..], &text[1..]),
+  }
+}
+
+fn matches(filter: &str, name: &str) -> bool {
+  let (filter, name) = (filter.to_lowercase(), name.to_lowercase());
+  if filter.contains(['*', '?']) {
+    let chars = |s: &str| s.chars().collect::<Vec<_>>();
+    glob(&chars(&filter), &chars(&name))
+  } else {
+    name.contains(&filter)
+  }
+}
 
 impl Handler for TzArgs {
-  fn handle<W, E>(&self, mut out: W, _err: E) -> Result<(), io::Error>
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
   where
     W: Write,
     E: Write,
   {
-    TZ_VARIANTS
+    if !self.resolve.is_empty() {
+      let mut code = ExitCode::SUCCESS;
+      for place in &self.resolve {
+        let zones = place_candidates(place);
+        if zones.is_empty() {
+          writeln!(&mut err, "{} is not a known timezone", place)?;
+          code = ExitCode::FAILURE;
+          continue;
+        }
+        let names = zones.iter().map(|tz| tz.name()).collect::<Vec<_>>();
+        writeln!(&mut out, "{}\t{}", place, names.join(" "))?;
+      }
+      return Ok(code);
+    }
+
+    let now = match &self.at {
+      None => Utc::now(),
+      Some(at) => {
+        let utc = Utc::now().with_timezone(&Zone::from(Tz::UTC));
+        let precision = match self.format.precision {
+          StampPrecision::Fixed(Precision::Millis) => StampPrecision::Auto,
+          given => given,
+        };
+        match at.to_dt(&precision, &utc) {
+          Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+          Ok(dt) => dt.with_timezone(&Utc),
+        }
+      }
+    };
+    if let Some(AutoTz(tz)) = &self.transitions {
+      let year = self.year.unwrap_or(now.year());
+      let changes = transitions(tz, year);
+      if changes.is_empty() {
+        return writeln!(&mut out, "{} has no transitions in {}", tz, year)
+          .map(|_| ExitCode::SUCCESS);
+      }
+      for at in changes {
+        let before = at - Duration::seconds(1);
+        let [from, from_abbr, _] = describe(tz, &before);
+        let [to, to_abbr, _] = describe(tz, &at);
+        let local = at.with_timezone(tz);
+        let when = if self.format.retains_stamps() {
+          local.format(DEFAULT_FORMAT).to_string()
+        } else {
+          match self.format.format(&local) {
+            Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+            Ok(when) => when,
+          }
+        };
+        writeln!(
+          &mut out,
+          "{}  {} {} -> {} {}",
+          when, from, from_abbr, to, to_abbr
+        )?;
+      }
+      return Ok(ExitCode::SUCCESS);
+    }
+    let mut zones = TZ_VARIANTS
       .iter()
-      .try_for_each(|f| writeln!(&mut out, "{}", f))
+      .filter(|tz| self.filter.as_ref().is_none_or(|f| matches(f, tz.name())))
+      .collect::<Vec<_>>();
+    match self.sort {
+      ZoneOrder::Name => zones.sort_by_key(|tz| tz.name()),
+      ZoneOrder::Offset => zones.sort_by_key(|tz| (offset_at(tz, &now), tz.name())),
+    }
+    if let Some(abbr) = &self.abbr {
+      let uses = zones
+        .iter()
+        .map(|tz| (tz.name(), abbr_offsets(tz, abbr, &now)))
+        .filter(|(_, offsets)| !offsets.is_empty())
+        .collect::<Vec<_>>();
+      if uses.is_empty() {
+        return writeln!(&mut err, "No timezones use {} this year", abbr)
+          .map(|_| ExitCode::FAILURE);
+      }
+      let width = uses.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+      for (name, offsets) in uses {
+        writeln!(&mut out, "{:<width$}  {}", name, offsets.join(" "))?;
+      }
+      return Ok(ExitCode::SUCCESS);
+    }
+    if zones.is_empty() {
+      let filter = self.filter.as_deref().unwrap_or_default();
+      return writeln!(&mut err, "No timezones match {}", filter).map(|_| ExitCode::FAILURE);
+    }
+    let long = self.long || self.at.is_some();
+    if !long && !self.now {
+      zones.iter().try_for_each(|f| writeln!(&mut out, "{}", f))?;
+      return Ok(ExitCode::SUCCESS);
+    }
+
+    let rows = zones
+      .iter()
+      .map(|tz| {
+        let mut row = vec![tz.name().to_string()];
+        if long {
+          row.extend(describe(tz, &now));
+        }
+        if self.now {
+          let local = now.with_timezone(*tz);
+          row.push(if self.format.retains_stamps() {
+            local.format(DEFAULT_FORMAT).to_string()
+          } else {
+            self.format.format(&local)?
+          });
+        }
+        Ok(row)
+      })
+      .collect::<Result<Vec<_>, String>>();
+    let rows = match rows {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(rows) => rows,
+    };
+    let widths = (0..rows[0].len())
+      .map(|i| rows.iter().map(|r| r[i].len()).max().unwrap_or(0))
+      .collect::<Vec<_>>();
+    for row in rows {
+      let cells = row
+        .iter()
+        .zip(&widths)
+        .map(|(cell, w)| format!("{:<w$}", cell))
+        .collect::<Vec<_>>();
+      writeln!(&mut out, "{}", cells.join("  ").trim_end())?;
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use chrono::{TimeZone, Utc};
+  use chrono_tz::Tz;
+
+  use super::matches;
+  use crate::run_test;
+  use indoc::indoc;
+
+  #[rstest]
+  #[case("america/ind", "America/Indiana/Knox", true)]
+  #[case("*kolkata*", "Asia/Kolkata", true)]
+  #[case("asia/*", "Asia/Kolkata", true)]
+  #[case("asia/*", "Europe/Asia", false)]
+  #[case("Etc/GMT+?", "Etc/GMT+5", true)]
+  #[case("Etc/GMT+?", "Etc/GMT+10", false)]
+  fn glob_or_substring(#[case] filter: &str, #[case] name: &str, #[case] expected: bool) {
+    assert_eq!(matches(filter, name), expected)
+  }
+
+  #[rstest]
+  #[case(Tz::America__New_York, 1679258022, ["-04:00", "EDT", "DST"])]
+  #[case(Tz::America__New_York, 1676258186, ["-05:00", "EST", "-"])]
+  #[case(Tz::Asia__Kolkata, 1679258022, ["+05:30", "IST", "-"])]
+  #[case(Tz::Australia__Lord_Howe, 1679258022, ["+11:00", "+11", "DST"])]
+  fn describe(#[case] tz: Tz, #[case] at: i64, #[case] expected: [&str; 3]) {
+    let at = Utc.timestamp_opt(at, 0).unwrap();
+    assert_eq!(super::describe(&tz, &at), expected.map(String::from))
+  }
+
+  #[test]
+  fn long() {
+    let (output, error) = run_test(" timezone --long asia/kolkata");
+    assert_eq!("", error);
+    assert_eq!("Asia/Kolkata  +05:30  IST  -\n", output);
+  }
+
+  #[test]
+  fn now() {
+    let (output, error) = run_test(" timezone --now -f=%z kolkata");
+    assert_eq!("", error);
+    assert_eq!("Asia/Kolkata  +0530\n", output);
+
+    let (output, error) = run_test(" timezone --now --long -f=%Z asia/kolkata");
+    assert_eq!("", error);
+    assert_eq!("Asia/Kolkata  +05:30  IST  -  IST\n", output);
+  }
+
+  #[test]
+  fn sort_offset() {
+    let (output, error) = run_test(" timezone --sort offset asia/k");
+    assert_eq!("", error);
+    let zones = output.lines().collect::<Vec<_>>();
+    let (kabul, kolkata) = (
+      zones.iter().position(|z| *z == "Asia/Kabul"),
+      zones.iter().position(|z| *z == "Asia/Kolkata"),
+    );
+    assert!(kabul < kolkata);
+    assert_eq!(zones.last(), Some(&"Asia/Kamchatka"));
+  }
+
+  #[rstest]
+  #[case(Tz::Asia__Kolkata, "IST", vec!["+05:30"])]
+  #[case(Tz::Europe__Dublin, "ist", vec!["+01:00"])]
+  #[case(Tz::America__New_York, "EST", vec!["-05:00"])]
+  #[case(Tz::America__New_York, "CET", vec![])]
+  fn abbr_offsets(#[case] tz: Tz, #[case] abbr: &str, #[case] expected: Vec<&str>) {
+    let at = Utc.timestamp_opt(1679258022, 0).unwrap();
+    assert_eq!(super::abbr_offsets(&tz, abbr, &at), expected)
+  }
+
+  #[test]
+  fn abbr() {
+    let (output, error) = run_test(" timezone --abbr IST asia/");
+    assert_eq!("", error);
+    assert!(output.contains("Asia/Kolkata    +05:30\n"));
+    assert!(output.contains("Asia/Jerusalem  +02:00\n"));
+
+    let (output, error) = run_test(" timezone --abbr XYZ");
+    assert_eq!("", output);
+    assert_eq!("No timezones use XYZ this year\n", error);
+  }
+
+  #[test]
+  fn transitions() {
+    let (output, error) = run_test(" timezone --transitions NYC --year 2025");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2025-03-09T03:00:00-0400  -05:00 EST -> -04:00 EDT
+        2025-11-02T01:00:00-0500  -04:00 EDT -> -05:00 EST
+      "},
+      output
+    );
+
+    let (output, error) = run_test(" timezone --transitions kolkata --year 2025");
+    assert_eq!("", error);
+    assert_eq!("Asia/Kolkata has no transitions in 2025\n", output);
+  }
+
+  #[test]
+  fn half_hour_transitions() {
+    let at = super::transitions(&Tz::Australia__Lord_Howe, 2025);
+    assert_eq!(
+      at.iter().map(|dt| dt.to_rfc3339()).collect::<Vec<_>>(),
+      vec!["2025-04-05T15:00:00+00:00", "2025-10-04T15:30:00+00:00"]
+    );
+  }
+
+  #[test]
+  fn at() {
+    let (output, error) = run_test(" timezone --at 1679258022 --now -f=%F Europe/Berlin");
+    assert_eq!("", error);
+    assert_eq!("Europe/Berlin  +01:00  CET  -  2023-03-19\n", output);
+
+    let (output, error) = run_test(" timezone --at 1688169600000 Europe/Berlin");
+    assert_eq!("", error);
+    assert_eq!("Europe/Berlin  +02:00  CEST  DST\n", output);
+
+    let (output, error) = run_test(" timezone -p days --at 19538 Europe/Berlin");
+    assert_eq!("", error);
+    assert_eq!("Europe/Berlin  +02:00  CEST  DST\n", output);
+
+    let (output, error) = run_test(" timezone --at 2023-07-01T00:00:00Z --now -f=%R Europe/Berlin");
+    assert_eq!("", error);
+    assert_eq!("Europe/Berlin  +02:00  CEST  DST  02:00\n", output);
+  }
+
+  #[test]
+  fn filtered() {
+    let (output, error) = run_test(" timezone *kolkata*");
+    assert_eq!("", error);
+    assert_eq!("Asia/Kolkata\n", output);
+
+    let (output, error) = run_test(" timezone atlantis");
+    assert_eq!("", output);
+    assert_eq!("No timezones match atlantis\n", error);
+  }
+
+  #[test]
+  fn resolve() {
+    let (output, error) = run_test(" timezone --resolve Paris IST Atlantis");
+    assert_eq!("Atlantis is not a known timezone\n", error);
+    assert_eq!(
+      indoc! {"
+        Paris\tEurope/Paris
+        IST\tAsia/Kolkata Europe/Dublin Asia/Jerusalem
+      "},
+      output
+    );
   }
 }
@@ -0,0 +1,105 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::Utc;
+use clap::Args;
+
+use crate::{
+  common::{
+    business_time, AtTimezoneArgs, BusinessHours, ConversionInput, DurationArgs, FormatArgs,
+    HolidayArgs,
+  },
+  Handler,
+};
+
+#[derive(Args)]
+pub struct UntilArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  duration: DurationArgs,
+
+  /// Only count time inside these daily hours on weekdays, such as
+  /// 09:00-17:00, evaluated in the target timezone
+  #[arg(long, short = 'b')]
+  business: Option<BusinessHours>,
+
+  #[command(flatten)]
+  holidays: HolidayArgs,
+
+  /// The time to count down to
+  #[arg(allow_negative_numbers = true)]
+  input: ConversionInput,
+}
+
+impl Handler for UntilArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    let target = match self.input.to_dt(&self.format.precision, &now) {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(dt) => dt.with_timezone(&into_tz),
+    };
+
+    let Some(hours) = &self.business else {
+      return match self.duration.format(target - now) {
+        Err(e) => writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(left) => writeln!(&mut out, "{}", left).map(|_| ExitCode::SUCCESS),
+      };
+    };
+    let holidays = match self.holidays.load() {
+      Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(holidays) => holidays,
+    };
+    let remaining = business_time(&now, &target, hours, &holidays);
+    let left = if self.duration.is_fixed() {
+      self.duration.format(remaining)
+    } else {
+      let per_day = hours.length().num_seconds();
+      let days = remaining.num_seconds() / per_day;
+      let rest = remaining - chrono::Duration::seconds(days * per_day);
+      let plural = if days.abs() == 1 { "" } else { "s" };
+      match rest.is_zero() {
+        true => Ok(format!("{} business day{}", days, plural)),
+        false => self
+          .duration
+          .format(rest)
+          .map(|rest| format!("{} business day{} {}", days, plural, rest)),
+      }
+    };
+    match left {
+      Err(e) => writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+      Ok(left) => writeln!(&mut out, "{}", left).map(|_| ExitCode::SUCCESS),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::run_test;
+
+  #[test]
+  fn passed() {
+    let (output, error) = run_test(" until -p secs -d secs 0");
+    assert_eq!("", error);
+    assert!(output.starts_with('-'));
+  }
+
+  #[test]
+  fn business_passed() {
+    let (output, error) = run_test(" until -p secs -b 09:00-17:00 0");
+    assert_eq!("", error);
+    assert!(output.starts_with('-'));
+    assert!(output.contains(" business days"));
+  }
+}
@@ -0,0 +1,165 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+  str::FromStr,
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use clap::Args;
+
+use crate::{
+  common::{AtTimezoneArgs, FormatArgs},
+  Handler,
+};
+
+/// The start of the Gregorian calendar, which v1 and v6 UUIDs count from in
+/// 100ns intervals, in seconds since 1970
+const GREGORIAN_EPOCH: i64 = -12_219_292_800;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Uuid(u128);
+
+impl FromStr for Uuid {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let trimmed = s.trim();
+    let bare = trimmed
+      .strip_prefix("urn:uuid:")
+      .unwrap_or(trimmed)
+      .trim_start_matches('{')
+      .trim_end_matches('}');
+    let hex = bare.replace('-', "");
+    if hex.len() != 32 {
+      return Err(format!("{} is not a UUID", s));
+    }
+    u128::from_str_radix(&hex, 16)
+      .map(Uuid)
+      .map_err(|_| format!("{} is not a UUID", s))
+  }
+}
+
+impl Uuid {
+  fn version(&self) -> u8 {
+    ((self.0 >> 76) & 0xf) as u8
+  }
+
+  /// When the UUID was generated, for the versions that embed it
+  fn time(&self) -> Result<DateTime<Utc>, String> {
+    let field = |shift: u32, bits: u32| (self.0 >> shift) as u64 & ((1 << bits) - 1);
+    let intervals = match self.version() {
+      // time_low, time_mid, then time_high around the version
+      1 => field(64, 12) << 48 | field(80, 16) << 32 | field(96, 32),
+      // The same fields, most significant first
+      6 => field(96, 32) << 28 | field(80, 16) << 12 | field(64, 12),
+      7 => {
+        return Utc
+          .timestamp_millis_opt(field(80, 48) as i64)
+          .single()
+          .ok_or_else(|| format!("{} is out of range", self));
+      }
+      v => {
+        return Err(format!(
+          "{} is a version {} UUID, which has no time",
+          self, v
+        ))
+      }
+    };
+    let nanos = GREGORIAN_EPOCH as i128 * 1_000_000_000 + intervals as i128 * 100;
+    Utc
+      .timestamp_opt(
+        nanos.div_euclid(1_000_000_000) as i64,
+        nanos.rem_euclid(1_000_000_000) as u32,
+      )
+      .single()
+      .ok_or_else(|| format!("{} is out of range", self))
+  }
+}
+
+impl std::fmt::Display for Uuid {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let hex = format!("{:032x}", self.0);
+    write!(
+      f,
+      "{}-{}-{}-{}-{}",
+      &hex[..8],
+      &hex[8..12],
+      &hex[12..16],
+      &hex[16..20],
+      &hex[20..]
+    )
+  }
+}
+
+#[derive(Args)]
+pub struct UuidArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Version 1, 6, or 7 UUIDs to print the generation time of
+  #[arg(required = true)]
+  uuids: Vec<Uuid>,
+}
+
+impl Handler for UuidArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    for uuid in &self.uuids {
+      match uuid
+        .time()
+        .and_then(|dt| self.format.format(&dt.with_timezone(&into_tz)))
+      {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(shown) => writeln!(&mut out, "{}", shown)?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use super::Uuid;
+  use crate::run_test;
+
+  #[rstest]
+  #[case(
+    "c232ab00-9414-11ec-b3c8-9f6bdeced846",
+    Ok("2022-02-22T19:22:22+00:00")
+  )]
+  #[case(
+    "{1EC9414C-232A-6B00-B3C8-9F6BDECED846}",
+    Ok("2022-02-22T19:22:22+00:00")
+  )]
+  #[case(
+    "017f22e2-79b0-7cc3-98c4-dc0c0c07398f",
+    Ok("2022-02-22T19:22:22+00:00")
+  )]
+  #[case(
+    "urn:uuid:919108f7-52d1-4320-9bac-f847db4148a8",
+    Err("919108f7-52d1-4320-9bac-f847db4148a8 is a version 4 UUID, which has no time")
+  )]
+  fn time(#[case] input: &str, #[case] expected: Result<&str, &str>) {
+    let uuid = input.parse::<Uuid>().unwrap();
+    assert_eq!(
+      uuid.time().map(|dt| dt.to_rfc3339()),
+      expected.map(String::from).map_err(String::from)
+    )
+  }
+
+  #[test]
+  fn formatted() {
+    let (output, error) = run_test(" uuid -t=NYC -f=%FT%T%z 017f22e2-79b0-7cc3-98c4-dc0c0c07398f");
+    assert_eq!("", error);
+    assert_eq!("2022-02-22T14:22:22-0500\n", output);
+  }
+}
@@ -0,0 +1,120 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{DateTime, Datelike, Days, Duration, NaiveTime, Utc};
+use clap::Args;
+
+use crate::{
+  common::{relocal, AtTimezoneArgs, ConversionInput, FormatArgs, WeekStart, WeekStartArgs, Zone},
+  Handler,
+};
+
+#[derive(Args)]
+pub struct WeekArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  #[command(flatten)]
+  week: WeekStartArgs,
+
+  /// Times to print the week of, along with the week's first and last
+  /// instants. Weeks starting on Monday are ISO weeks
+  #[arg(allow_negative_numbers = true, default_value = "now")]
+  input: Vec<ConversionInput>,
+}
+
+/// The week holding the time, as its label and first and last instants.
+/// Monday weeks are labelled by ISO week, and Sunday weeks by the week of
+/// the year their first day falls in, as %U counts them
+fn week(
+  dt: &DateTime<Zone>,
+  week_start: WeekStart,
+) -> Result<(String, DateTime<Zone>, DateTime<Zone>), String> {
+  let date = dt.date_naive();
+  let first = date.checked_sub_days(Days::new(week_start.days_into(date.weekday()) as u64));
+  let next = first.and_then(|d| d.checked_add_days(Days::new(7)));
+  let (Some(first), Some(next)) = (first, next) else {
+    return Err(format!("The week of {} is out of range", dt));
+  };
+  let label = match week_start {
+    WeekStart::Mon => {
+      let iso = date.iso_week();
+      format!("{}-W{:02}", iso.year(), iso.week())
+    }
+    WeekStart::Sun => first.format("%Y-W%U").to_string(),
+  };
+  let start = relocal(dt, &first.and_time(NaiveTime::MIN))?;
+  let end = relocal(dt, &next.and_time(NaiveTime::MIN))? - Duration::nanoseconds(1);
+  Ok((label, start, end))
+}
+
+impl Handler for WeekArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    for input in &self.input {
+      let weeked = input
+        .to_dt(&self.format.precision, &now)
+        .and_then(|dt| week(&dt.with_timezone(&into_tz), self.week.get()))
+        .and_then(|(label, start, end)| {
+          Ok((
+            label,
+            self.format.format(&start)?,
+            self.format.format(&end)?,
+          ))
+        });
+      match weeked {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok((label, start, end)) => writeln!(&mut out, "{} {} {}", label, start, end)?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use indoc::indoc;
+
+  use crate::run_test;
+
+  #[test]
+  fn weeks() {
+    // The first is a Saturday, and the last belongs to the prior ISO year
+    let (output, error) = run_test(
+      " week -t=UTC -p secs -f=%FT%T 1689379200 2023-03-19T12:00:00Z 2021-01-01T00:00:00Z",
+    );
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2023-W28 2023-07-10T00:00:00 2023-07-16T23:59:59
+        2023-W11 2023-03-13T00:00:00 2023-03-19T23:59:59
+        2020-W53 2020-12-28T00:00:00 2021-01-03T23:59:59
+      "},
+      output
+    );
+  }
+
+  #[test]
+  fn sunday_weeks() {
+    let (output, error) =
+      run_test(" week -t=UTC -p secs -f=%FT%T --week-start sun 1689379200 2023-03-19T12:00:00Z");
+    assert_eq!("", error);
+    assert_eq!(
+      indoc! {"
+        2023-W28 2023-07-09T00:00:00 2023-07-15T23:59:59
+        2023-W12 2023-03-19T00:00:00 2023-03-25T23:59:59
+      "},
+      output
+    );
+  }
+}
@@ -0,0 +1,91 @@
+use std::{
+  io::{self, Write},
+  process::ExitCode,
+};
+
+use chrono::{Datelike, Utc, Weekday};
+use clap::{Args, ValueEnum};
+
+use crate::{
+  common::{AtTimezoneArgs, ConversionInput, FormatArgs},
+  Handler,
+};
+
+/// How weekdays are numbered
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Numbering {
+  /// Monday is 1 through to Sunday as 7, as ISO 8601 and date +%u do
+  Iso,
+  /// Sunday is 0 through to Saturday as 6, as cron and date +%w do
+  Sunday,
+}
+
+impl Numbering {
+  fn number(&self, day: Weekday) -> u32 {
+    match self {
+      Numbering::Iso => day.number_from_monday(),
+      Numbering::Sunday => day.num_days_from_sunday(),
+    }
+  }
+}
+
+#[derive(Args)]
+pub struct WeekdayArgs {
+  #[command(flatten)]
+  timezone: AtTimezoneArgs,
+
+  #[command(flatten)]
+  format: FormatArgs,
+
+  /// Print weekdays as numbers rather than names
+  #[arg(value_enum, long, short = 'n')]
+  numeric: Option<Numbering>,
+
+  /// Times to print the day of the week of
+  #[arg(allow_negative_numbers = true, default_value = "now")]
+  input: Vec<ConversionInput>,
+}
+
+impl Handler for WeekdayArgs {
+  fn handle<W, E>(&self, mut out: W, mut err: E) -> Result<ExitCode, io::Error>
+  where
+    W: Write,
+    E: Write,
+  {
+    let into_tz = self.timezone.get();
+    let now = Utc::now().with_timezone(&into_tz);
+    for input in &self.input {
+      let dt = match input.to_dt(&self.format.precision, &now) {
+        Err(e) => return writeln!(&mut err, "{}", e).map(|_| ExitCode::FAILURE),
+        Ok(dt) => dt.with_timezone(&into_tz),
+      };
+      match self.numeric {
+        Some(numbering) => writeln!(&mut out, "{}", numbering.number(dt.weekday()))?,
+        None => writeln!(&mut out, "{}", dt.format("%A"))?,
+      }
+    }
+    Ok(ExitCode::SUCCESS)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rstest::*;
+
+  use crate::run_test;
+
+  #[rstest]
+  #[case("", "Sunday\nMonday\n")]
+  #[case(" -n iso", "7\n1\n")]
+  #[case(" -n sunday", "0\n1\n")]
+  fn weekdays(#[case] flags: &str, #[case] expected: &str) {
+    // Late Sunday in New York is already Monday in UTC
+    let cmd = format!(
+      " weekday -t=America/New_York{} 2023-03-19T22:00:00-04:00 2023-03-20T12:00:00Z",
+      flags
+    );
+    let (output, error) = run_test(&cmd);
+    assert_eq!("", error);
+    assert_eq!(expected, output);
+  }
+}